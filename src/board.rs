@@ -0,0 +1,206 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::taiga::{TaigaStatus, TaigaTask};
+use crate::utils::slug;
+
+lazy_static! {
+    static ref REFERENCE_REGEX: Regex = Regex::new(r"^(.*?)\s+#(\d+)$").unwrap();
+}
+
+#[derive(Debug, Clone)]
+pub struct BoardItem {
+    pub line: usize,
+    pub status: String,
+    pub checked: bool,
+    pub name: String,
+    pub reference: Option<i32>,
+}
+
+// Parse a board file, with one `## status` heading per status and a checklist item per story
+pub fn parse_board(input: &str) -> Result<Vec<BoardItem>, (usize, String)> {
+    let mut items = Vec::new();
+    let mut status: Option<String> = None;
+
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+
+        if let Some(heading) = line.strip_prefix("## ") {
+            // headings are status slugs, whose dashes slug would drop
+            status = Some(slug(heading.trim().replace('-', " ")));
+            continue;
+        }
+
+        let item = match line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            Some(item) => item.trim_start(),
+            None => continue,
+        };
+
+        let (checked, text) = if let Some(text) = item.strip_prefix("[ ]") {
+            (false, text.trim())
+        } else if let Some(text) = item
+            .strip_prefix("[x]")
+            .or_else(|| item.strip_prefix("[X]"))
+        {
+            (true, text.trim())
+        } else {
+            continue;
+        };
+
+        let status = status
+            .clone()
+            .ok_or_else(|| (i + 1, "item outside of a status heading".to_string()))?;
+
+        let (name, reference) = match REFERENCE_REGEX.captures(text) {
+            Some(caps) => (caps[1].to_string(), caps[2].parse().ok()),
+            None => (text.to_string(), None),
+        };
+        if name.is_empty() {
+            return Err((i + 1, "empty checklist item".to_string()));
+        }
+
+        items.push(BoardItem {
+            line: i + 1,
+            status,
+            checked,
+            name,
+            reference,
+        });
+    }
+
+    Ok(items)
+}
+
+pub fn render_board(title: &str, statuses: &[TaigaStatus], tasks: &[TaigaTask]) -> String {
+    let mut output = format!("# {}\n", title);
+
+    for status in statuses {
+        output.push_str(&format!("\n## {}\n", status.slug));
+
+        let check = if status.is_closed { "x" } else { " " };
+        let items: Vec<String> = tasks
+            .iter()
+            .filter(|task| task.status_id == status.id)
+            .map(|task| format!("- [{}] {} #{}\n", check, task.name, task.reference))
+            .collect();
+        if !items.is_empty() {
+            output.push('\n');
+            output.push_str(&items.concat());
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(id: i32, slug: &str, is_closed: bool) -> TaigaStatus {
+        TaigaStatus {
+            id,
+            slug: slug.to_string(),
+            is_closed,
+            color: String::new(),
+            order: id,
+            wip_limit: None,
+            is_archived: false,
+        }
+    }
+
+    fn task(reference: i32, name: &str, status: &TaigaStatus) -> TaigaTask {
+        TaigaTask {
+            id: reference + 100,
+            reference,
+            name: name.to_string(),
+            status_id: status.id,
+            status: status.slug.clone(),
+            team: false,
+            client: false,
+            blocked: false,
+            assigned: Vec::new(),
+            due: None,
+            swimlane: None,
+            closed: status.is_closed,
+            tags: Vec::new(),
+            watchers: Vec::new(),
+            votes: 0,
+            version: 1,
+            attributes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn parses_checkboxes_and_references() {
+        let input = "# Web\n\n## new\n\n- [ ] Fix login #12\n* [x] Update footer\n- [X] Ship #3\n";
+        let items = parse_board(input).unwrap();
+
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].status, "new");
+        assert_eq!(items[0].name, "Fix login");
+        assert_eq!(items[0].reference, Some(12));
+        assert!(!items[0].checked);
+        assert_eq!(items[0].line, 5);
+        assert_eq!(items[1].name, "Update footer");
+        assert_eq!(items[1].reference, None);
+        assert!(items[1].checked);
+        assert!(items[2].checked);
+    }
+
+    #[test]
+    fn reads_dashed_status_headings() {
+        let items = parse_board("## in-progress\n- [ ] Fix login #12\n").unwrap();
+        assert_eq!(items[0].status, "in-progress");
+
+        let items = parse_board("## In Progress\n- [ ] Fix login #12\n").unwrap();
+        assert_eq!(items[0].status, "in-progress");
+    }
+
+    #[test]
+    fn keeps_hashes_inside_names() {
+        let items = parse_board("## new\n- [ ] Support C# #4\n- [ ] Issue#5\n").unwrap();
+        assert_eq!(items[0].name, "Support C#");
+        assert_eq!(items[0].reference, Some(4));
+        assert_eq!(items[1].name, "Issue#5");
+        assert_eq!(items[1].reference, None);
+    }
+
+    #[test]
+    fn ignores_other_lines() {
+        let items = parse_board("Some notes\n- plain item\n## new\n- [-] maybe\n").unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn reports_misplaced_and_empty_items() {
+        assert_eq!(parse_board("- [ ] Fix login\n").unwrap_err().0, 1);
+        assert_eq!(parse_board("## new\n- [ ]\n").unwrap_err().0, 2);
+    }
+
+    #[test]
+    fn round_trips_rendered_boards() {
+        let statuses = vec![
+            status(1, "new", false),
+            status(2, "in-progress", false),
+            status(3, "done", true),
+        ];
+        let tasks = vec![
+            task(12, "Fix login", &statuses[0]),
+            task(13, "Review #7 fallout", &statuses[1]),
+            task(9, "Update footer", &statuses[2]),
+        ];
+
+        let output = render_board("Web", &statuses, &tasks);
+        assert!(output.starts_with("# Web\n\n## new\n\n- [ ] Fix login #12\n"));
+        assert!(output.contains("## done\n\n- [x] Update footer #9\n"));
+
+        let items = parse_board(&output).unwrap();
+        assert_eq!(items.len(), 3);
+        for (item, task) in items.iter().zip(&tasks) {
+            assert_eq!(item.name, task.name);
+            assert_eq!(item.reference, Some(task.reference));
+            assert_eq!(item.status, task.status);
+            assert_eq!(item.checked, task.closed);
+        }
+    }
+}
@@ -1,20 +1,17 @@
-use std::{cmp::Ordering, env, process::exit};
+use std::{cmp::Ordering, collections::HashSet, env, fmt, process::exit};
 
 use colored::Colorize;
 use prettytable::{format::FormatBuilder, Cell, Row, Table};
 
-use crate::taiga::Taiga;
+use crate::git::{branch_reference, current_branch, repository_project};
+use crate::import::ImportFormat;
+use crate::taiga::{parse_interval, parse_minutes, Taiga, TaigaConfig, TaigaTasks, TaigaTemplate};
 
 #[derive(Debug)]
 pub struct LoginArgs {
     pub address: Option<String>,
 }
 
-#[derive(Debug)]
-pub struct ProjectArgs {
-    pub project: String,
-}
-
 #[derive(Debug)]
 pub struct NewTaskArgs {
     pub project: String,
@@ -22,35 +19,45 @@ pub struct NewTaskArgs {
     pub name: String,
     pub assign: Vec<String>,
     pub due_date: Option<String>,
+    pub recur: Option<String>,
     pub team: bool,
     pub client: bool,
     pub block: bool,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub points: Option<f64>,
 }
 
 #[derive(Debug)]
 pub struct MoveTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub status: String,
 }
 
 #[derive(Debug)]
 pub struct DoneTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
+}
+
+#[derive(Debug)]
+pub struct ReopenTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
 }
 
 #[derive(Debug)]
 pub struct RenameTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub name: String,
 }
 
 #[derive(Debug)]
 pub struct AssignTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub username: String,
     pub remove: bool,
 }
@@ -58,47 +65,86 @@ pub struct AssignTaskArgs {
 #[derive(Debug)]
 pub struct DueTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub due_date: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct TeamTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub remove: bool,
 }
 
 #[derive(Debug)]
 pub struct ClientTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub remove: bool,
 }
 
 #[derive(Debug)]
 pub struct BlockTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub remove: bool,
 }
 
 #[derive(Debug)]
 pub struct ModifyTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
     pub status: Option<String>,
     pub rename: Option<String>,
-    pub assign: Option<Vec<String>>,
+    pub assign: Option<AssignChange>,
     pub due_date: Option<String>,
     pub team: Option<bool>,
     pub client: Option<bool>,
     pub block: Option<bool>,
 }
 
+// How modify changes the assignees: replace them, then add and remove users
+#[derive(Debug, Default)]
+pub struct AssignChange {
+    pub replace: Option<Vec<String>>,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
 #[derive(Debug)]
 pub struct SearchTaskArgs {
     pub project: String,
+    pub filter: TaskFilter,
+    pub watch: Option<u64>,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TaskSelector {
+    Ids(Vec<usize>),
+    Filter(TaskFilter),
+    Reference(i32),
+}
+
+impl fmt::Display for TaskSelector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TaskSelector::Ids(ids) => write!(
+                f,
+                "{}",
+                ids.iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            TaskSelector::Filter(_) => write!(f, "<FILTER>"),
+            TaskSelector::Reference(reference) => write!(f, "#{}", reference),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TaskFilter {
     pub include_statuses: Vec<String>,
     pub exclude_statuses: Vec<String>,
     pub include_assigned: Vec<String>,
@@ -107,6 +153,10 @@ pub struct SearchTaskArgs {
     pub team: Option<bool>,
     pub client: Option<bool>,
     pub block: Option<bool>,
+    pub watching: Vec<String>,
+    pub attributes: Vec<(String, String)>,
+    pub closed: bool,
+    pub archived: bool,
     pub query: Vec<String>,
 }
 
@@ -115,13 +165,220 @@ pub struct ProjectUserArgs {
     pub project: String,
 }
 
+#[derive(Debug)]
+pub struct ProjectRecurArgs {
+    pub project: String,
+}
+
+#[derive(Debug)]
+pub struct ProjectBoardArgs {
+    pub project: String,
+}
+
+#[derive(Debug)]
+pub enum StatusAction {
+    Add {
+        name: String,
+        color: Option<String>,
+        closed: bool,
+        wip_limit: Option<i32>,
+    },
+    Rename {
+        status: String,
+        name: String,
+    },
+    Reorder {
+        statuses: Vec<String>,
+    },
+    Close {
+        status: String,
+        closed: bool,
+    },
+    Delete {
+        status: String,
+        move_to: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+pub struct ProjectStatusArgs {
+    pub project: String,
+    pub action: StatusAction,
+}
+
+#[derive(Debug)]
+pub enum MemberAction {
+    Invite { email: String, role: String },
+    Remove { username: String },
+}
+
+#[derive(Debug)]
+pub struct ProjectMemberArgs {
+    pub project: String,
+    pub action: MemberAction,
+}
+
+#[derive(Debug)]
+pub struct ProjectNewArgs {
+    pub name: String,
+    pub template: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ImportStoriesArgs {
+    pub project: String,
+    pub path: Option<String>,
+    pub format: ImportFormat,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ExportFormat {
+    Taskwarrior,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct ProjectExportArgs {
+    pub project: String,
+    pub format: ExportFormat,
+}
+
+#[derive(Debug)]
+pub struct ProjectImportArgs {
+    pub project: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct ProjectSyncArgs {
+    pub project: String,
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct WebhookServeArgs {
+    pub host: String,
+    pub port: u16,
+    pub key: String,
+}
+
+#[derive(Debug)]
+pub struct IcsArgs {
+    pub project: Option<String>,
+    pub path: Option<String>,
+    pub events: bool,
+}
+
+#[derive(Debug)]
+pub struct ProjectRestoreArgs {
+    pub project: String,
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct AttachTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub path: String,
+}
+
+#[derive(Debug)]
+pub struct ListAttachmentsArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+}
+
+#[derive(Debug)]
+pub struct GetAttachmentArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub index: usize,
+    pub output: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct WatchTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub remove: bool,
+}
+
+#[derive(Debug)]
+pub struct VoteTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub remove: bool,
+}
+
+#[derive(Debug)]
+pub struct ShowTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+}
+
+#[derive(Debug)]
+pub struct CommentTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct SetAttributeArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub values: Vec<(String, String)>,
+}
+
+#[derive(Debug)]
+pub struct BranchTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub start: bool,
+}
+
+#[derive(Debug)]
+pub struct StopTaskArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub note: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct LogTimeArgs {
+    pub project: String,
+    pub selector: TaskSelector,
+    pub minutes: i64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct TimesheetArgs {
+    pub project: Option<String>,
+    pub week: bool,
+    pub push: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct InstallHookArgs {
+    pub project: String,
+}
+
+#[derive(Debug)]
+pub struct CommitMsgArgs {
+    pub project: String,
+    pub path: String,
+}
+
 #[derive(Debug)]
 pub struct DeleteTaskArgs {
     pub project: String,
-    pub id: usize,
+    pub selector: TaskSelector,
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 pub struct ProjectBurndownArgs {
     pub project: String,
 }
@@ -134,6 +391,7 @@ pub enum TaigaCmd {
     NewTask(NewTaskArgs),
     MoveTask(MoveTaskArgs),
     DoneTask(DoneTaskArgs),
+    ReopenTask(ReopenTaskArgs),
     RenameTask(RenameTaskArgs),
     AssignTask(AssignTaskArgs),
     DueTask(DueTaskArgs),
@@ -143,11 +401,38 @@ pub enum TaigaCmd {
     ModifyTask(ModifyTaskArgs),
     SearchTask(SearchTaskArgs),
     DeleteTask(DeleteTaskArgs),
+    AttachTask(AttachTaskArgs),
+    ListAttachments(ListAttachmentsArgs),
+    GetAttachment(GetAttachmentArgs),
+    WatchTask(WatchTaskArgs),
+    VoteTask(VoteTaskArgs),
+    ShowTask(ShowTaskArgs),
+    CommentTask(CommentTaskArgs),
+    SetAttribute(SetAttributeArgs),
+    BranchTask(BranchTaskArgs),
+    StopTask(StopTaskArgs),
+    LogTime(LogTimeArgs),
+    Timesheet(TimesheetArgs),
+    InstallHook(InstallHookArgs),
+    CommitMsg(CommitMsgArgs),
     ProjectUsers(ProjectUserArgs),
+    ProjectBoard(ProjectBoardArgs),
+    ProjectStatus(ProjectStatusArgs),
+    ProjectMember(ProjectMemberArgs),
+    ProjectNew(ProjectNewArgs),
+    ProjectRecur(ProjectRecurArgs),
+    ImportStories(ImportStoriesArgs),
+    ProjectExport(ProjectExportArgs),
+    ProjectImport(ProjectImportArgs),
+    ProjectSync(ProjectSyncArgs),
+    ProjectRestore(ProjectRestoreArgs),
+    Ics(IcsArgs),
+    WebhookServe(WebhookServeArgs),
+    #[allow(dead_code)]
     ProjectBurndown(ProjectBurndownArgs),
 }
 
-pub fn parse_args(taiga: &Option<Taiga>) -> TaigaCmd {
+pub fn parse_args(taiga: &Option<Taiga>, config: &TaigaConfig) -> TaigaCmd {
     let args: Vec<String> = env::args().skip(1).collect();
 
     if args.is_empty() {
@@ -171,8 +456,13 @@ pub fn parse_args(taiga: &Option<Taiga>) -> TaigaCmd {
     match verb.as_str() {
         "login" => cli_login(&args[1..]),
         "projects" => cli_projects(&args[1..]),
+        "ics" => cli_ics(None, &args[1..]),
+        "." => cli_current(taiga, config, &args[1..]),
+        "timesheet" => cli_timesheet(None, &args[1..]),
+        "webhook" => cli_webhook(&args[1..]),
+        "project" => cli_project_create(&args[1..]),
         "--help" => {
-            cli_help(allowed_projects);
+            cli_help(allowed_projects, config);
             exit(0);
         }
         "--version" => {
@@ -180,13 +470,77 @@ pub fn parse_args(taiga: &Option<Taiga>) -> TaigaCmd {
             exit(0);
         }
         project => {
-            let project = project.to_string();
-            if allowed_projects.contains(&project) {
-                cli_project(project, &args[1..])
-            } else {
-                eprintln!("Error, not a valid command");
-                exit(1);
+            let is_project = allowed_projects.iter().any(|p| p == project)
+                || config.aliases.contains_key(project);
+
+            if let Some(default_project) = &config.default_project {
+                if !is_project && is_project_command(project) {
+                    let default_project = resolve_project(taiga, config, default_project);
+                    return cli_project(default_project, &args, config);
+                }
             }
+
+            let project = resolve_project(taiga, config, project);
+            cli_project(project, &args[1..], config)
+        }
+    }
+}
+
+// Verbs and modifiers that can directly follow a project name
+const PROJECT_COMMANDS: &[&str] = &[
+    "add",
+    "new",
+    "move",
+    "done",
+    "reopen",
+    "rename",
+    "assign",
+    "due",
+    "team",
+    "client",
+    "block",
+    "mod",
+    "modify",
+    "del",
+    "delete",
+    "search",
+    "burndown",
+    "board",
+    "status",
+    "member",
+    "users",
+    "recur",
+    "import-stories",
+    "export",
+    "import",
+    "sync-file",
+    "ics",
+    "restore",
+    "install-hook",
+    "commit-msg",
+    "timesheet",
+];
+
+fn is_project_command(arg: &str) -> bool {
+    PROJECT_COMMANDS.contains(&arg)
+        || parse_ids(arg).is_some()
+        || arg.starts_with('@')
+        || arg.starts_with('+')
+        || arg.starts_with('-')
+        || arg.contains(':')
+}
+
+fn resolve_project(taiga: &Option<Taiga>, config: &TaigaConfig, name: &str) -> String {
+    let taiga = taiga.as_ref().unwrap_or_else(|| {
+        eprintln!("Error, not a valid command");
+        exit(1);
+    });
+
+    match taiga.resolve_project(name, config) {
+        Ok(project) => project.name,
+        Err(err) => {
+            eprintln!("Error, {}", err);
+            exit(1);
         }
     }
 }
@@ -230,6 +584,96 @@ fn cli_login_help() {
     help_message.display();
 }
 
+fn cli_webhook(args: &[String]) -> TaigaCmd {
+    if args.first().map(String::as_str) != Some("serve") || args.contains(&"--help".to_string()) {
+        cli_webhook_help();
+        exit(if args.contains(&"--help".to_string()) {
+            0
+        } else {
+            1
+        });
+    }
+
+    let mut host = "127.0.0.1".to_string();
+    let mut port = 8080;
+    let mut key = None;
+
+    let mut args = args[1..].iter();
+    while let Some(arg) = args.next() {
+        let value = args.next().unwrap_or_else(|| {
+            cli_webhook_help();
+            exit(1);
+        });
+        match arg.as_str() {
+            "--host" => host = value.to_string(),
+            "--port" => {
+                port = value.parse().unwrap_or_else(|_| {
+                    eprintln!("Invalid port {}", value);
+                    exit(1);
+                })
+            }
+            "--key" => key = Some(value.to_string()),
+            _ => {
+                cli_webhook_help();
+                exit(1);
+            }
+        }
+    }
+
+    let key = key.unwrap_or_else(|| {
+        eprintln!("The webhook key is required");
+        exit(1);
+    });
+
+    TaigaCmd::WebhookServe(WebhookServeArgs { host, port, key })
+}
+
+fn cli_webhook_help() {
+    let mut help_message = HelpMessage::new(
+        "Receive Taiga webhooks and keep the cache up to date",
+        "taiga webhook serve",
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--key <KEY>", "The secret key of the webhook");
+    help_message.add_command("--port <PORT>", "The port to listen on [default: 8080]");
+    help_message.add_command(
+        "--host <HOST>",
+        "The address to listen on [default: 127.0.0.1]",
+    );
+    help_message.add_command("--help", "Print help message and exit");
+    help_message.display();
+}
+
+// Run a command on the project of the current repository, task commands on the story of the branch
+fn cli_current(taiga: &Option<Taiga>, config: &TaigaConfig, args: &[String]) -> TaigaCmd {
+    let name = repository_project().unwrap_or_else(|| {
+        eprintln!("Error, no project for this directory, add a .taiga file or set taiga.project in the git config");
+        exit(1);
+    });
+    let project = resolve_project(taiga, config, &name);
+
+    match args.first() {
+        Some(verb) if TASK_COMMANDS.contains(&verb.as_str()) => {
+            let branch = current_branch().unwrap_or_else(|err| {
+                eprintln!("Error, {}", err);
+                exit(1);
+            });
+            let reference = branch_reference(&branch).unwrap_or_else(|| {
+                eprintln!("Error, the branch {} does not name a story", branch);
+                exit(1);
+            });
+            cli_project_task(
+                project,
+                TaskSelector::Reference(reference),
+                verb,
+                &args[1..],
+            )
+        }
+        _ => cli_project(project, args, config),
+    }
+}
+
 fn cli_projects(args: &[String]) -> TaigaCmd {
     if !args.is_empty() {
         cli_login_projects();
@@ -247,7 +691,7 @@ fn cli_login_projects() {
     help_message.display();
 }
 
-fn cli_project(project: String, args: &[String]) -> TaigaCmd {
+fn cli_project(project: String, args: &[String], config: &TaigaConfig) -> TaigaCmd {
     if args.is_empty() {
         return cli_project_search(project, args);
     }
@@ -255,82 +699,90 @@ fn cli_project(project: String, args: &[String]) -> TaigaCmd {
     let verb = args.first().expect("Could not get verb");
 
     match verb.as_str() {
-        "add" | "new" => cli_project_new(project, &args[1..]),
+        "add" | "new" => cli_project_new(project, &args[1..], config),
         "move" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_move(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_move(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "done" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_done(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_done(project, TaskSelector::Ids(ids), &args[2..])
+            } else {
+                cli_help_project(project);
+                exit(1);
+            }
+        }
+        "reopen" => {
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_reopen(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "rename" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_rename(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_rename(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "assign" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_assign(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_assign(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "due" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_due(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_due(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "team" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_team(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_team(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "client" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_client(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_client(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "block" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_block(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_block(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "mod" | "modify" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_modify(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_modify(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
             }
         }
         "del" | "delete" => {
-            if let Some(id) = args.get(1).and_then(|id| id.parse::<usize>().ok()) {
-                cli_project_delete(project, id, &args[2..])
+            if let Some(ids) = args.get(1).and_then(|ids| task_ids(&project, ids)) {
+                cli_project_delete(project, TaskSelector::Ids(ids), &args[2..])
             } else {
                 cli_help_project(project);
                 exit(1);
@@ -338,34 +790,47 @@ fn cli_project(project: String, args: &[String]) -> TaigaCmd {
         }
         "search" => cli_project_search(project, &args[1..]),
         "burndown" => cli_project_burndown(project, &args[1..]),
+        "board" => cli_project_board(project, &args[1..]),
+        "status" => cli_project_status(project, &args[1..]),
+        "member" => cli_project_member(project, &args[1..]),
         "users" => cli_project_users(project, &args[1..]),
+        "recur" => cli_project_recur(project, &args[1..]),
+        "import-stories" => cli_project_import_stories(project, &args[1..]),
+        "export" => cli_project_export(project, &args[1..]),
+        "import" => cli_project_import(project, &args[1..]),
+        "sync-file" => cli_project_sync(project, &args[1..]),
+        "ics" => cli_ics(Some(project), &args[1..]),
+        "restore" => cli_project_restore(project, &args[1..]),
+        "install-hook" => cli_project_install_hook(project, &args[1..]),
+        "commit-msg" => cli_project_commit_msg(project, &args[1..]),
+        "timesheet" => cli_timesheet(Some(project), &args[1..]),
         "--help" => {
             cli_help_project(project);
             exit(0);
         }
         id => {
-            if let Ok(id) = id.parse::<usize>() {
+            if let Some(ids) = task_ids(&project, id) {
                 if let Some(new_verb) = args.get(1) {
-                    match new_verb.as_str() {
-                        "move" => cli_project_move(project, id, &args[2..]),
-                        "done" => cli_project_done(project, id, &args[2..]),
-                        "rename" => cli_project_rename(project, id, &args[2..]),
-                        "assign" => cli_project_assign(project, id, &args[2..]),
-                        "due" => cli_project_due(project, id, &args[2..]),
-                        "team" => cli_project_team(project, id, &args[2..]),
-                        "client" => cli_project_client(project, id, &args[2..]),
-                        "block" => cli_project_block(project, id, &args[2..]),
-                        "mod" | "modify" => cli_project_modify(project, id, &args[2..]),
-                        "del" | "delete" => cli_project_delete(project, id, &args[2..]),
-                        _ => {
-                            cli_help_project(project);
-                            exit(1);
-                        }
-                    }
+                    cli_project_task(project, TaskSelector::Ids(ids), new_verb, &args[2..])
                 } else {
                     cli_help_project(project);
                     exit(1);
                 }
+            } else if let Some(command) = filter_command(args) {
+                let (end, position) = command.unwrap_or_else(|verb| {
+                    eprintln!(
+                        "Error, {} is also a search word, run it with `taiga {} <MODIFIERS> -- {}` or search for it with `taiga {} search`",
+                        verb, project, verb, project
+                    );
+                    exit(1);
+                });
+                let filter = parse_filter(project.clone(), &args[..end]);
+                cli_project_task(
+                    project,
+                    TaskSelector::Filter(filter),
+                    &args[position],
+                    &args[position + 1..],
+                )
             } else {
                 cli_project_search(project, args)
             }
@@ -373,24 +838,197 @@ fn cli_project(project: String, args: &[String]) -> TaigaCmd {
     }
 }
 
-fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
+// Verbs that act on a selection of tasks
+const TASK_COMMANDS: &[&str] = &[
+    "move",
+    "done",
+    "reopen",
+    "rename",
+    "assign",
+    "due",
+    "team",
+    "client",
+    "block",
+    "mod",
+    "modify",
+    "del",
+    "delete",
+    "attach",
+    "attachments",
+    "attachment",
+    "watch",
+    "unwatch",
+    "upvote",
+    "downvote",
+    "show",
+    "comment",
+    "set",
+    "branch",
+    "start",
+    "stop",
+    "log",
+];
+
+// Commands that are also common search words, only taken as commands after a `--`
+const SEARCH_WORD_COMMANDS: &[&str] = &["show", "set", "log"];
+
+// Where the modifiers end and the command starts in `<MODIFIERS> [--] <COMMAND>`, or the
+// command when it could as well be a search word
+fn filter_command(args: &[String]) -> Option<Result<(usize, usize), &str>> {
+    let position = args
+        .iter()
+        .position(|arg| arg == "--" || TASK_COMMANDS.contains(&arg.as_str()))?;
+    if !args[..position].iter().all(|arg| is_filter_modifier(arg)) {
+        return None;
+    }
+
+    let verb = args[position].as_str();
+    if verb == "--" {
+        return match args.get(position + 1) {
+            Some(verb) if TASK_COMMANDS.contains(&verb.as_str()) => {
+                Some(Ok((position, position + 1)))
+            }
+            _ => None,
+        };
+    }
+    if SEARCH_WORD_COMMANDS.contains(&verb) {
+        return Some(Err(verb));
+    }
+    Some(Ok((position, position)))
+}
+
+fn cli_project_task(
+    project: String,
+    selector: TaskSelector,
+    verb: &str,
+    args: &[String],
+) -> TaigaCmd {
+    match verb {
+        "move" => cli_project_move(project, selector, args),
+        "done" => cli_project_done(project, selector, args),
+        "reopen" => cli_project_reopen(project, selector, args),
+        "rename" => cli_project_rename(project, selector, args),
+        "assign" => cli_project_assign(project, selector, args),
+        "due" => cli_project_due(project, selector, args),
+        "team" => cli_project_team(project, selector, args),
+        "client" => cli_project_client(project, selector, args),
+        "block" => cli_project_block(project, selector, args),
+        "mod" | "modify" => cli_project_modify(project, selector, args),
+        "del" | "delete" => cli_project_delete(project, selector, args),
+        "attach" => cli_project_attach(project, selector, args),
+        "attachments" => cli_project_attachments(project, selector, args),
+        "attachment" => cli_project_attachment(project, selector, args),
+        "watch" => cli_project_watch(project, selector, args, false),
+        "unwatch" => cli_project_watch(project, selector, args, true),
+        "upvote" => cli_project_vote(project, selector, args, false),
+        "downvote" => cli_project_vote(project, selector, args, true),
+        "show" => cli_project_show(project, selector, args),
+        "comment" => cli_project_comment(project, selector, args),
+        "set" => cli_project_set(project, selector, args),
+        "branch" => cli_project_branch(project, selector, args, false),
+        "start" => cli_project_branch(project, selector, args, true),
+        "stop" => cli_project_stop(project, selector, args),
+        "log" => cli_project_log(project, selector, args),
+        _ => {
+            cli_help_project(project);
+            exit(1);
+        }
+    }
+}
+
+// Parse a list of card ids such as 3,5-8 into its ranges
+pub fn parse_ids(arg: &str) -> Option<Vec<(usize, usize)>> {
+    let mut ranges = Vec::new();
+    for part in arg.split(',') {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.parse::<usize>().ok()?, end.parse::<usize>().ok()?),
+            None => {
+                let id = part.parse::<usize>().ok()?;
+                (id, id)
+            }
+        };
+        if start == 0 || start > end {
+            return None;
+        }
+        ranges.push((start, end));
+    }
+    Some(ranges)
+}
+
+// Expand id ranges into distinct ids in the given order, all within the cached tasks
+pub fn expand_ids(ranges: &[(usize, usize)], count: usize) -> Option<Vec<usize>> {
+    if ranges.iter().any(|(_, end)| *end > count) {
+        return None;
+    }
+
+    let mut seen = HashSet::new();
+    Some(
+        ranges
+            .iter()
+            .flat_map(|(start, end)| *start..=*end)
+            .filter(|id| seen.insert(*id))
+            .collect(),
+    )
+}
+
+// Parse the ids of the cached tasks of a project a command applies to
+fn task_ids(project: &str, arg: &str) -> Option<Vec<usize>> {
+    let ranges = parse_ids(arg)?;
+
+    let count = Taiga::from_cache()
+        .and_then(|taiga| taiga.projects.into_iter().find(|p| p.name == project))
+        .and_then(|project| TaigaTasks::from_cache(project.id))
+        .map_or(0, |tasks| tasks.tasks.len());
+
+    Some(expand_ids(&ranges, count).unwrap_or_else(|| {
+        eprintln!("Invalid task for this project");
+        exit(1);
+    }))
+}
+
+fn is_filter_modifier(arg: &str) -> bool {
+    arg.starts_with('@') || arg.starts_with('+') || arg.starts_with('-') || arg.contains(':')
+}
+
+fn cli_project_new(project: String, args: &[String], config: &TaigaConfig) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
         cli_project_new_help(project);
         exit(0);
     }
 
     let mut options = Vec::new();
+    let mut templates = Vec::new();
     let mut statuses = Vec::new();
     let mut assigned = Vec::new();
     let mut dues = Vec::new();
+    let mut recurs = Vec::new();
     let mut teams = Vec::new();
     let mut clients = Vec::new();
     let mut blocks = Vec::new();
+    let mut descriptions = Vec::new();
+    let mut tag_lists = Vec::new();
+    let mut points_list = Vec::new();
     let mut others = Vec::new();
     let mut can_continuous = true;
 
-    for arg in args {
-        if arg.starts_with("--") {
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--template" {
+            if let Some(template) = args_iter.next() {
+                templates.push(template.clone());
+            } else {
+                cli_project_new_help(project);
+                exit(1);
+            }
+            if !others.is_empty() {
+                can_continuous = false;
+            }
+        } else if let Some(template) = arg.strip_prefix("--template=") {
+            templates.push(template.to_string());
+            if !others.is_empty() {
+                can_continuous = false;
+            }
+        } else if arg.starts_with("--") {
             options.push(arg);
             if !others.is_empty() {
                 can_continuous = false;
@@ -410,6 +1048,11 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
             if !others.is_empty() {
                 can_continuous = false;
             }
+        } else if arg.starts_with("recur:") {
+            recurs.push(arg);
+            if !others.is_empty() {
+                can_continuous = false;
+            }
         } else if arg.starts_with('+') || arg.starts_with('-') {
             match arg.as_str() {
                 "+team" => teams.push(true),
@@ -426,6 +1069,21 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
             if !others.is_empty() {
                 can_continuous = false;
             }
+        } else if let Some(description) = arg.strip_prefix("desc:") {
+            descriptions.push(description.to_string());
+            if !others.is_empty() {
+                can_continuous = false;
+            }
+        } else if let Some(tags) = arg.strip_prefix("tags:") {
+            tag_lists.push(tags.to_string());
+            if !others.is_empty() {
+                can_continuous = false;
+            }
+        } else if let Some(points) = arg.strip_prefix("points:") {
+            points_list.push(points.to_string());
+            if !others.is_empty() {
+                can_continuous = false;
+            }
         } else if arg.contains(':') {
             cli_project_new_help(project);
             exit(1);
@@ -443,22 +1101,37 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
         exit(1);
     }
 
-    let team = match teams.len().cmp(&1) {
-        Ordering::Equal => *teams.first().expect("Could not get team"),
+    let template = match templates.len().cmp(&1) {
+        Ordering::Equal => {
+            let name = templates.first().expect("Could not get template");
+            config.templates.get(name).cloned().unwrap_or_else(|| {
+                eprintln!("Error, could not find template '{}'", name);
+                exit(1);
+            })
+        }
         Ordering::Greater => {
             cli_project_new_help(project);
             exit(1);
         }
-        Ordering::Less => false,
+        Ordering::Less => TaigaTemplate::default(),
     };
 
-    let client = match clients.len().cmp(&1) {
-        Ordering::Equal => *clients.first().expect("Could not get client"),
-        Ordering::Greater => {
+    let team = match teams.len().cmp(&1) {
+        Ordering::Equal => *teams.first().expect("Could not get team"),
+        Ordering::Greater => {
+            cli_project_new_help(project);
+            exit(1);
+        }
+        Ordering::Less => template.team.unwrap_or(false),
+    };
+
+    let client = match clients.len().cmp(&1) {
+        Ordering::Equal => *clients.first().expect("Could not get client"),
+        Ordering::Greater => {
             cli_project_new_help(project);
             exit(1);
         }
-        Ordering::Less => false,
+        Ordering::Less => template.client.unwrap_or(false),
     };
 
     let block = match blocks.len().cmp(&1) {
@@ -467,7 +1140,7 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
             cli_project_new_help(project);
             exit(1);
         }
-        Ordering::Less => false,
+        Ordering::Less => template.block.unwrap_or(false),
     };
 
     let due_date = match dues.len().cmp(&1) {
@@ -488,6 +1161,27 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
         Ordering::Less => None,
     };
 
+    let recur = match recurs.len().cmp(&1) {
+        Ordering::Equal => {
+            let recur_str = *recurs.first().expect("Could not get recur");
+            let interval = &recur_str["recur:".len()..];
+            if parse_interval(interval).is_none() {
+                eprintln!("Error, could not parse recurrence interval '{}'", interval);
+                exit(1);
+            }
+            if due_date.is_none() {
+                eprintln!("Error, a recurring task needs a due date");
+                exit(1);
+            }
+            Some(interval.to_string())
+        }
+        Ordering::Greater => {
+            cli_project_new_help(project);
+            exit(1);
+        }
+        Ordering::Less => None,
+    };
+
     let status = match statuses.len().cmp(&1) {
         Ordering::Equal => {
             let status_str = *statuses.first().expect("Could not get due");
@@ -510,11 +1204,11 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
             cli_project_new_help(project);
             exit(1);
         }
-        Ordering::Less => None,
+        Ordering::Less => template.status.clone(),
     };
 
     let assign = if assigned.is_empty() {
-        Vec::new()
+        template.assign.clone()
     } else {
         let assigned = assigned
             .iter()
@@ -531,11 +1225,53 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
         assigned
     };
 
+    // an empty description or tag list clears the one of the template
+    let description = match descriptions.len().cmp(&1) {
+        Ordering::Equal => Some(descriptions.remove(0)).filter(|d| !d.is_empty()),
+        Ordering::Greater => {
+            cli_project_new_help(project);
+            exit(1);
+        }
+        Ordering::Less => template.description.clone(),
+    };
+
+    let tags = match tag_lists.len().cmp(&1) {
+        Ordering::Equal => tag_lists[0]
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        Ordering::Greater => {
+            cli_project_new_help(project);
+            exit(1);
+        }
+        Ordering::Less => template.tags.clone(),
+    };
+
+    let points = match points_list.len().cmp(&1) {
+        Ordering::Equal => match points_list[0].parse::<f64>() {
+            Ok(points) => Some(points),
+            Err(_) => {
+                eprintln!("Error, could not parse points '{}'", points_list[0]);
+                exit(1);
+            }
+        },
+        Ordering::Greater => {
+            cli_project_new_help(project);
+            exit(1);
+        }
+        Ordering::Less => template.points,
+    };
+
     let name = if others.is_empty() {
         cli_project_new_help(project);
         exit(1);
     } else {
-        others.join(" ")
+        format!(
+            "{}{}",
+            template.prefix.clone().unwrap_or_default(),
+            others.join(" ")
+        )
     };
 
     TaigaCmd::NewTask(NewTaskArgs {
@@ -544,9 +1280,13 @@ fn cli_project_new(project: String, args: &[String]) -> TaigaCmd {
         name,
         assign,
         due_date,
+        recur,
         team,
         client,
         block,
+        description,
+        tags,
+        points,
     })
 }
 
@@ -563,38 +1303,46 @@ fn cli_project_new_help(project: String) {
     );
     help_message.add_command("@<USERNAME>", "The user to assign the task to");
     help_message.add_command("due:<DATE>", "The due date to give to the task");
+    help_message.add_command(
+        "recur:<INTERVAL>",
+        "Recreate the task when done (daily, weekly, monthly, 2w...)",
+    );
     help_message.add_command("+/-team", "Set or unset the team requirement");
     help_message.add_command("+/-client", "Set or unset the client requirement");
     help_message.add_command("+/-block", "Set or unset the block");
+    help_message.add_command("desc:<TEXT>", "The description of the task");
+    help_message.add_command("tags:<TAGS>", "The tags of the task, separated by commas");
+    help_message.add_command("points:<POINTS>", "The points of the task");
     help_message.add_command("...", "The name for the task");
     help_message.add_section("Options");
+    help_message.add_command("--template <NAME>", "Use a template from the configuration");
     help_message.add_command("--help", "Print the help message and exit");
     help_message.display();
 }
 
-fn cli_project_move(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_move(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_move_help(project, id);
+        cli_project_move_help(project, &selector);
         exit(0);
     }
 
     if args.len() != 1 {
-        cli_project_move_help(project, id);
+        cli_project_move_help(project, &selector);
         exit(1);
     }
 
     let status = args.first().expect("Could not get status").to_string();
     TaigaCmd::MoveTask(MoveTaskArgs {
         project,
-        id,
+        selector,
         status,
     })
 }
 
-fn cli_project_move_help(project: String, id: usize) {
+fn cli_project_move_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Move a task to a status",
-        &format!("taiga {} move {}", project, id),
+        &format!("taiga {} move {}", project, selector),
         "<ARGS> <OPTIONS>",
     );
     help_message.add_section("Arguments");
@@ -604,24 +1352,49 @@ fn cli_project_move_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_done(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_done(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_done_help(project, id);
+        cli_project_done_help(project, &selector);
         exit(0);
     }
 
     if !args.is_empty() {
-        cli_project_done_help(project, id);
+        cli_project_done_help(project, &selector);
         exit(1);
     }
 
-    TaigaCmd::DoneTask(DoneTaskArgs { project, id })
+    TaigaCmd::DoneTask(DoneTaskArgs { project, selector })
 }
 
-fn cli_project_done_help(project: String, id: usize) {
+fn cli_project_done_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Declare a task as done",
-        &format!("taiga {} done {}", project, id),
+        &format!("taiga {} done {}", project, selector),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_reopen(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_reopen_help(project, &selector);
+        exit(0);
+    }
+
+    if !args.is_empty() {
+        cli_project_reopen_help(project, &selector);
+        exit(1);
+    }
+
+    TaigaCmd::ReopenTask(ReopenTaskArgs { project, selector })
+}
+
+fn cli_project_reopen_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Move a closed task back to the first open status",
+        &format!("taiga {} reopen {}", project, selector),
         "<OPTIONS>",
     );
     help_message.add_section("Options");
@@ -629,24 +1402,24 @@ fn cli_project_done_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_delete(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_delete(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_delete_help(project, id);
+        cli_project_delete_help(project, &selector);
         exit(0);
     }
 
     if !args.is_empty() {
-        cli_project_delete_help(project, id);
+        cli_project_delete_help(project, &selector);
         exit(1);
     }
 
-    TaigaCmd::DeleteTask(DeleteTaskArgs { project, id })
+    TaigaCmd::DeleteTask(DeleteTaskArgs { project, selector })
 }
 
-fn cli_project_delete_help(project: String, id: usize) {
+fn cli_project_delete_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Delete a task",
-        &format!("taiga {} delete {}", project, id),
+        &format!("taiga {} delete {}", project, selector),
         "<OPTIONS>",
     );
     help_message.add_section("Options");
@@ -654,16 +1427,16 @@ fn cli_project_delete_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_rename(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_rename(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_rename_help(project, id);
+        cli_project_rename_help(project, &selector);
         exit(0);
     }
 
     let contains_options = args.iter().any(|s| s.starts_with("--"));
 
     if args.is_empty() || contains_options {
-        cli_project_rename_help(project, id);
+        cli_project_rename_help(project, &selector);
         exit(1);
     }
 
@@ -673,13 +1446,17 @@ fn cli_project_rename(project: String, id: usize, args: &[String]) -> TaigaCmd {
         .collect::<Vec<&str>>()
         .join(" ");
 
-    TaigaCmd::RenameTask(RenameTaskArgs { project, id, name })
+    TaigaCmd::RenameTask(RenameTaskArgs {
+        project,
+        selector,
+        name,
+    })
 }
 
-fn cli_project_rename_help(project: String, id: usize) {
+fn cli_project_rename_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Rename a task",
-        &format!("taiga {} rename {}", project, id),
+        &format!("taiga {} rename {}", project, selector),
         "<ARGS> <OPTIONS>",
     );
     help_message.add_section("Arguments");
@@ -689,9 +1466,9 @@ fn cli_project_rename_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_assign(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_assign(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_assign_help(project, id);
+        cli_project_assign_help(project, &selector);
         exit(0);
     }
 
@@ -717,28 +1494,28 @@ fn cli_project_assign(project: String, id: usize, args: &[String]) -> TaigaCmd {
     {
         remove = true;
     } else if !options.is_empty() {
-        cli_project_assign_help(project, id);
+        cli_project_assign_help(project, &selector);
         exit(1);
     }
 
     if others.len() != 1 {
-        cli_project_assign_help(project, id);
+        cli_project_assign_help(project, &selector);
         exit(1);
     }
 
     let username = others.first().expect("Could not get status").to_string();
     TaigaCmd::AssignTask(AssignTaskArgs {
         project,
-        id,
+        selector,
         username,
         remove,
     })
 }
 
-fn cli_project_assign_help(project: String, id: usize) {
+fn cli_project_assign_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Assign a task",
-        &format!("taiga {} assign {}", project, id),
+        &format!("taiga {} assign {}", project, selector),
         "<ARGS> <OPTIONS>",
     );
     help_message.add_section("Arguments");
@@ -749,9 +1526,9 @@ fn cli_project_assign_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_due(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_due(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_due_help(project, id);
+        cli_project_due_help(project, &selector);
         exit(0);
     }
 
@@ -776,20 +1553,20 @@ fn cli_project_due(project: String, id: usize, args: &[String]) -> TaigaCmd {
         if others.is_empty() {
             return TaigaCmd::DueTask(DueTaskArgs {
                 project,
-                id,
+                selector,
                 due_date: None,
             });
         } else {
-            cli_project_due_help(project, id);
+            cli_project_due_help(project, &selector);
             exit(1);
         }
     } else if !options.is_empty() {
-        cli_project_due_help(project, id);
+        cli_project_due_help(project, &selector);
         exit(1);
     }
 
     if others.len() != 1 {
-        cli_project_assign_help(project, id);
+        cli_project_assign_help(project, &selector);
         exit(1);
     }
 
@@ -801,15 +1578,15 @@ fn cli_project_due(project: String, id: usize, args: &[String]) -> TaigaCmd {
 
     TaigaCmd::DueTask(DueTaskArgs {
         project,
-        id,
+        selector,
         due_date: Some(date),
     })
 }
 
-fn cli_project_due_help(project: String, id: usize) {
+fn cli_project_due_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Set due date for a task",
-        &format!("taiga {} due {}", project, id),
+        &format!("taiga {} due {}", project, selector),
         "<ARGS> <OPTIONS>",
     );
     help_message.add_section("Arguments");
@@ -820,9 +1597,9 @@ fn cli_project_due_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_team(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_team(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_team_help(project, id);
+        cli_project_team_help(project, &selector);
         exit(0);
     }
 
@@ -838,7 +1615,7 @@ fn cli_project_team(project: String, id: usize, args: &[String]) -> TaigaCmd {
     }
 
     if !others.is_empty() {
-        cli_project_team_help(project, id);
+        cli_project_team_help(project, &selector);
         exit(1);
     }
 
@@ -851,7 +1628,7 @@ fn cli_project_team(project: String, id: usize, args: &[String]) -> TaigaCmd {
     {
         true
     } else if !options.is_empty() {
-        cli_project_team_help(project, id);
+        cli_project_team_help(project, &selector);
         exit(1);
     } else {
         false
@@ -859,15 +1636,15 @@ fn cli_project_team(project: String, id: usize, args: &[String]) -> TaigaCmd {
 
     TaigaCmd::TeamTask(TeamTaskArgs {
         project,
-        id,
+        selector,
         remove,
     })
 }
 
-fn cli_project_team_help(project: String, id: usize) {
+fn cli_project_team_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Toggle team requirement for a task",
-        &format!("taiga {} team {}", project, id),
+        &format!("taiga {} team {}", project, selector),
         "<OPTIONS>",
     );
     help_message.add_section("Options");
@@ -876,9 +1653,9 @@ fn cli_project_team_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_client(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_client(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_client_help(project, id);
+        cli_project_client_help(project, &selector);
         exit(0);
     }
 
@@ -894,7 +1671,7 @@ fn cli_project_client(project: String, id: usize, args: &[String]) -> TaigaCmd {
     }
 
     if !others.is_empty() {
-        cli_project_client_help(project, id);
+        cli_project_client_help(project, &selector);
         exit(1);
     }
 
@@ -907,7 +1684,7 @@ fn cli_project_client(project: String, id: usize, args: &[String]) -> TaigaCmd {
     {
         true
     } else if !options.is_empty() {
-        cli_project_client_help(project, id);
+        cli_project_client_help(project, &selector);
         exit(1);
     } else {
         false
@@ -915,15 +1692,15 @@ fn cli_project_client(project: String, id: usize, args: &[String]) -> TaigaCmd {
 
     TaigaCmd::ClientTask(ClientTaskArgs {
         project,
-        id,
+        selector,
         remove,
     })
 }
 
-fn cli_project_client_help(project: String, id: usize) {
+fn cli_project_client_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Toggle client requirement for a task",
-        &format!("taiga {} client {}", project, id),
+        &format!("taiga {} client {}", project, selector),
         "<OPTIONS>",
     );
     help_message.add_section("Options");
@@ -932,9 +1709,9 @@ fn cli_project_client_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_block(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_block(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_block_help(project, id);
+        cli_project_block_help(project, &selector);
         exit(0);
     }
 
@@ -950,7 +1727,7 @@ fn cli_project_block(project: String, id: usize, args: &[String]) -> TaigaCmd {
     }
 
     if !others.is_empty() {
-        cli_project_block_help(project, id);
+        cli_project_block_help(project, &selector);
         exit(1);
     }
 
@@ -963,7 +1740,7 @@ fn cli_project_block(project: String, id: usize, args: &[String]) -> TaigaCmd {
     {
         true
     } else if !options.is_empty() {
-        cli_project_block_help(project, id);
+        cli_project_block_help(project, &selector);
         exit(1);
     } else {
         false
@@ -971,15 +1748,15 @@ fn cli_project_block(project: String, id: usize, args: &[String]) -> TaigaCmd {
 
     TaigaCmd::BlockTask(BlockTaskArgs {
         project,
-        id,
+        selector,
         remove,
     })
 }
 
-fn cli_project_block_help(project: String, id: usize) {
+fn cli_project_block_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Toggle block for a task",
-        &format!("taiga {} block {}", project, id),
+        &format!("taiga {} block {}", project, selector),
         "<OPTIONS>",
     );
     help_message.add_section("Options");
@@ -988,9 +1765,9 @@ fn cli_project_block_help(project: String, id: usize) {
     help_message.display();
 }
 
-fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
+fn cli_project_modify(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
     if args.contains(&"--help".to_string()) {
-        cli_project_modify_help(project, id);
+        cli_project_modify_help(project, &selector);
         exit(0);
     }
 
@@ -998,6 +1775,7 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
 
     let mut statuses = Vec::new();
     let mut assigned = Vec::new();
+    let mut assign_changes = Vec::new();
 
     let mut dues = Vec::new();
 
@@ -1024,6 +1802,11 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
             if !others.is_empty() {
                 can_continuous = false;
             }
+        } else if let Some(change) = arg.strip_prefix("assign:") {
+            assign_changes.push(change);
+            if !others.is_empty() {
+                can_continuous = false;
+            }
         } else if arg.starts_with("due:") {
             dues.push(arg);
             if !others.is_empty() {
@@ -1058,14 +1841,14 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
     }
 
     if !options.is_empty() {
-        cli_project_modify_help(project, id);
+        cli_project_modify_help(project, &selector);
         exit(1);
     }
 
     let team = match teams.len().cmp(&1) {
         Ordering::Equal => Some(*teams.first().expect("Could not get team")),
         Ordering::Greater => {
-            cli_project_modify_help(project, id);
+            cli_project_modify_help(project, &selector);
             exit(1);
         }
         Ordering::Less => None,
@@ -1074,7 +1857,7 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
     let client = match clients.len().cmp(&1) {
         Ordering::Equal => Some(*clients.first().expect("Could not get client")),
         Ordering::Greater => {
-            cli_project_modify_help(project, id);
+            cli_project_modify_help(project, &selector);
             exit(1);
         }
         Ordering::Less => None,
@@ -1083,7 +1866,7 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
     let block = match blocks.len().cmp(&1) {
         Ordering::Equal => Some(*blocks.first().expect("Could not get block")),
         Ordering::Greater => {
-            cli_project_modify_help(project, id);
+            cli_project_modify_help(project, &selector);
             exit(1);
         }
         Ordering::Less => None,
@@ -1094,14 +1877,14 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
             let due_str = *dues.first().expect("Could not get due");
             let date_rest = &due_str["due:".len()..];
             if date_rest.is_empty() {
-                cli_project_modify_help(project, id);
+                cli_project_modify_help(project, &selector);
                 exit(1);
             }
             let due_date = temporis::parse_date(date_rest);
             Some(due_date.unwrap().format("%Y-%m-%d").to_string())
         }
         Ordering::Greater => {
-            cli_project_modify_help(project, id);
+            cli_project_modify_help(project, &selector);
             exit(1);
         }
         Ordering::Less => None,
@@ -1126,28 +1909,19 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
             Some(status_rest.to_string())
         }
         Ordering::Greater => {
-            cli_project_modify_help(project, id);
+            cli_project_modify_help(project, &selector);
             exit(1);
         }
         Ordering::Less => None,
     };
 
-    let assign = if assigned.is_empty() {
+    let assign = if assigned.is_empty() && assign_changes.is_empty() {
         None
     } else {
-        let assigned = assigned
-            .iter()
-            .map(|string| {
-                let sub_str = &string[1..];
-                if sub_str.is_empty() {
-                    cli_project_modify_help(project.clone(), id);
-                    exit(1);
-                }
-                sub_str.to_string()
-            })
-            .collect::<Vec<String>>();
-
-        Some(assigned)
+        Some(parse_assign(&assigned, &assign_changes).unwrap_or_else(|| {
+            cli_project_modify_help(project.clone(), &selector);
+            exit(1);
+        }))
     };
 
     let rename = if others.is_empty() {
@@ -1158,7 +1932,7 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
 
     TaigaCmd::ModifyTask(ModifyTaskArgs {
         project,
-        id,
+        selector,
         status,
         rename,
         assign,
@@ -1169,15 +1943,61 @@ fn cli_project_modify(project: String, id: usize, args: &[String]) -> TaigaCmd {
     })
 }
 
-fn cli_project_modify_help(project: String, id: usize) {
+// Gather the @user and assign: modifiers, or None when they do not make sense together
+fn parse_assign(assigned: &[&String], changes: &[&str]) -> Option<AssignChange> {
+    let mut change = AssignChange::default();
+    for string in assigned {
+        let sub_str = &string[1..];
+        if sub_str.is_empty() {
+            return None;
+        }
+        change.add.push(sub_str.to_string());
+    }
+
+    for assign_change in changes {
+        // `assign:` alone clears the assignees, `=` replaces them, `+` and `-` edit them
+        let (names, list) = if let Some(names) = assign_change.strip_prefix('=') {
+            (names, None)
+        } else if let Some(names) = assign_change.strip_prefix('+') {
+            (names, Some(&mut change.add))
+        } else if let Some(names) = assign_change.strip_prefix('-') {
+            (names, Some(&mut change.remove))
+        } else if assign_change.is_empty() {
+            ("", None)
+        } else {
+            (*assign_change, Some(&mut change.add))
+        };
+
+        let names = names
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_string())
+            .collect::<Vec<String>>();
+        match list {
+            Some(list) if !names.is_empty() => list.extend(names),
+            None if change.replace.is_none() => change.replace = Some(names),
+            _ => return None,
+        }
+    }
+
+    Some(change)
+}
+
+fn cli_project_modify_help(project: String, selector: &TaskSelector) {
     let mut help_message = HelpMessage::new(
         "Toggle block for a task",
-        &format!("taiga {} modify {}", project, id),
+        &format!("taiga {} modify {}", project, selector),
         "<MODIFIERS> <OPTIONS> ...",
     );
     help_message.add_section("Modifiers");
     help_message.add_command("status:<STATUS>", "The status to move the task to");
     help_message.add_command("@<USERNAME>", "The user to assign the task to");
+    help_message.add_command("assign:=<USERS>", "Replace the assignees, comma separated");
+    help_message.add_command(
+        "assign:+/-<USERS>",
+        "Add or remove assignees, comma separated",
+    );
+    help_message.add_command("assign:", "Remove all the assignees");
     help_message.add_command("due:<DATE>", "The due date to give to the task");
     help_message.add_command("+/-team", "Add or remove the team requirement");
     help_message.add_command("+/-client", "Add or remove the client requirement");
@@ -1194,6 +2014,85 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
         exit(0);
     }
 
+    let mut watches = Vec::new();
+    let mut columns = Vec::new();
+    let mut modifiers = Vec::new();
+
+    let mut args_iter = args.iter().peekable();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--watch" {
+            match args_iter
+                .peek()
+                .and_then(|interval| parse_seconds(interval))
+            {
+                Some(interval) => {
+                    watches.push(interval);
+                    args_iter.next();
+                }
+                None => watches.push(30),
+            }
+        } else if let Some(interval) = arg.strip_prefix("--watch=") {
+            match parse_seconds(interval) {
+                Some(interval) => watches.push(interval),
+                None => {
+                    cli_project_search_help(project);
+                    exit(1);
+                }
+            }
+        } else if arg == "--attr" {
+            match args_iter.next() {
+                Some(name) if !name.starts_with("--") => columns.push(name.clone()),
+                _ => {
+                    cli_project_search_help(project);
+                    exit(1);
+                }
+            }
+        } else if let Some(name) = arg.strip_prefix("--attr=") {
+            if name.is_empty() {
+                cli_project_search_help(project);
+                exit(1);
+            }
+            columns.push(name.to_string());
+        } else {
+            modifiers.push(arg.clone());
+        }
+    }
+
+    let watch = match watches.len().cmp(&1) {
+        Ordering::Equal => watches.first().copied(),
+        Ordering::Greater => {
+            cli_project_search_help(project);
+            exit(1);
+        }
+        Ordering::Less => None,
+    };
+
+    let filter = parse_filter(project.clone(), &modifiers);
+    TaigaCmd::SearchTask(SearchTaskArgs {
+        project,
+        filter,
+        watch,
+        columns,
+    })
+}
+
+// Parse a refresh interval such as 30, 30s, 2m or 1h into seconds
+fn parse_seconds(interval: &str) -> Option<u64> {
+    let (count, unit) = match interval.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => interval.split_at(index),
+        None => (interval, "s"),
+    };
+    let count = count.parse::<u64>().ok().filter(|count| *count > 0)?;
+
+    match unit {
+        "s" => Some(count),
+        "m" => Some(count * 60),
+        "h" => Some(count * 60 * 60),
+        _ => None,
+    }
+}
+
+fn parse_filter(project: String, args: &[String]) -> TaskFilter {
     let mut options = Vec::new();
 
     let mut include_statuses = Vec::new();
@@ -1208,11 +2107,21 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
     let mut clients = Vec::new();
     let mut blocks = Vec::new();
 
+    let mut watching = Vec::new();
+    let mut attributes = Vec::new();
+    let mut closed = false;
+    let mut archived = false;
+
     let mut query = Vec::new();
     let mut can_continuous = true;
 
     for arg in args {
-        if arg.starts_with("--") {
+        if arg == "--archived" {
+            archived = true;
+            if !query.is_empty() {
+                can_continuous = false;
+            }
+        } else if arg.starts_with("--") {
             options.push(arg);
             if !query.is_empty() {
                 can_continuous = false;
@@ -1262,6 +2171,11 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
             if !query.is_empty() {
                 can_continuous = false;
             }
+        } else if arg == "+closed" {
+            closed = true;
+            if !query.is_empty() {
+                can_continuous = false;
+            }
         } else if arg == "+block" {
             blocks.push(true);
             if !query.is_empty() {
@@ -1272,6 +2186,28 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
             if !query.is_empty() {
                 can_continuous = false;
             }
+        } else if let Some(username) = arg.strip_prefix("watching:") {
+            if username.is_empty() {
+                cli_project_search_help(project);
+                exit(1);
+            }
+            watching.push(username.to_string());
+            if !query.is_empty() {
+                can_continuous = false;
+            }
+        } else if let Some(attribute) = arg.strip_prefix("attr.") {
+            match attribute.split_once(':') {
+                Some((name, value)) if !name.is_empty() => {
+                    attributes.push((name.to_string(), value.to_string()))
+                }
+                _ => {
+                    cli_project_search_help(project);
+                    exit(1);
+                }
+            }
+            if !query.is_empty() {
+                can_continuous = false;
+            }
         } else if arg.contains(':') {
             cli_project_new_help(project);
             exit(1);
@@ -1294,7 +2230,7 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
         exit(1);
     }
 
-    let include_statuses = include_statuses
+    let mut include_statuses = include_statuses
         .iter()
         .map(|status_str| {
             let status_rest = if let Some(rest) = status_str.strip_prefix("status:") {
@@ -1312,6 +2248,12 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
         })
         .collect::<Vec<String>>();
 
+    // status:all keeps every status, closed ones included
+    if include_statuses.iter().any(|status| status == "all") {
+        include_statuses.retain(|status| status != "all");
+        closed = true;
+    }
+
     let exclude_statuses = exclude_statuses
         .iter()
         .map(|status_str| {
@@ -1407,8 +2349,7 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
         Ordering::Less => None,
     };
 
-    TaigaCmd::SearchTask(SearchTaskArgs {
-        project,
+    TaskFilter {
         include_statuses,
         exclude_statuses,
         include_assigned,
@@ -1417,8 +2358,12 @@ fn cli_project_search(project: String, args: &[String]) -> TaigaCmd {
         team,
         client,
         block,
+        watching,
+        attributes,
+        closed,
+        archived,
         query,
-    })
+    }
 }
 
 fn cli_project_search_help(project: String) {
@@ -1429,6 +2374,7 @@ fn cli_project_search_help(project: String) {
     );
     help_message.add_section("Modifiers");
     help_message.add_command("status:<STATUS>", "A status the task is in");
+    help_message.add_command("status:all", "Any status, closed ones included");
     help_message.add_command("-status:<STATUS>", "A status the task is not in");
     help_message.add_command("@<USERNAME>", "A username that the task is assigned to");
     help_message.add_command(
@@ -1442,8 +2388,20 @@ fn cli_project_search_help(project: String) {
     help_message.add_command("+/-team", "Filter team requirement");
     help_message.add_command("+/-client", "Filter client requirement");
     help_message.add_command("+/-block", "Filter blocked tasks");
+    help_message.add_command("+closed", "Include closed tasks");
+    help_message.add_command("watching:<USERNAME>", "A username watching the task");
+    help_message.add_command(
+        "attr.<NAME>:<VALUE>",
+        "A custom attribute value - empty for unset",
+    );
     help_message.add_command("...", "A query for the tasks");
     help_message.add_section("Options");
+    help_message.add_command(
+        "--watch [INTERVAL]",
+        "Refresh the results in place, like 30s or 2m [default: 30s]",
+    );
+    help_message.add_command("--attr <NAME>", "Add a column for a custom attribute");
+    help_message.add_command("--archived", "Include tasks in archived statuses");
     help_message.add_command("--help", "Print the help message and exit");
     help_message.display();
 }
@@ -1453,79 +2411,1209 @@ fn cli_project_users(project: String, _args: &[String]) -> TaigaCmd {
     TaigaCmd::ProjectUsers(ProjectUserArgs { project })
 }
 
-fn cli_project_burndown(project: String, _args: &[String]) -> TaigaCmd {
-    // TODO: implement help
-    TaigaCmd::ProjectBurndown(ProjectBurndownArgs { project })
+fn cli_project_board(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_board_help(project);
+        exit(0);
+    }
+
+    if !args.is_empty() {
+        cli_project_board_help(project);
+        exit(1);
+    }
+
+    TaigaCmd::ProjectBoard(ProjectBoardArgs { project })
 }
 
-fn cli_help_project(project: String) {
+fn cli_project_board_help(project: String) {
     let mut help_message = HelpMessage::new(
-        &format!("Run command on {}", project),
-        &format!("taiga {}", project),
-        "[COMMAND] <OPTIONS>",
+        "Show the kanban board of the project, by swimlane",
+        &format!("taiga {} board", project),
+        "<OPTIONS>",
     );
-    help_message.add_section("Commands");
-    help_message.add_command("new", "Create a new task");
-    help_message.add_command("move <CARD-ID>", "Move a task to a status");
-    help_message.add_command("done <CARD-ID>", "Declare a task as done");
-    help_message.add_command("rename <CARD-ID>", "Rename a task");
-    help_message.add_command("assign <CARD-ID>", "Assign a task");
-    help_message.add_command("due <CARD-ID>", "Set due date for a task");
-    help_message.add_command("team <CARD-ID>", "Toggle team requirement for a task");
-    help_message.add_command("client <CARD-ID>", "Toggle client requirement for a task");
-    help_message.add_command("block <CARD-ID>", "Toggle block for a task");
-    help_message.add_command("modify <CARD-ID>", "Modify a task");
-    help_message.add_command("delete <CARD-ID>", "Delete a task");
-    help_message.add_command("search", "Search for tasks that fit requirements");
-    help_message.add_command("users", "List users for the project");
-    help_message.add_command("burndown", "List statistics for the project");
     help_message.add_section("Options");
     help_message.add_command("--help", "Print the help message and exit");
     help_message.display();
 }
 
-fn cli_help(allowed_projects: Vec<String>) {
-    let mut help_message = HelpMessage::new(
-        "Cli tool for taiga.io",
-        "taiga",
-        "[PROJECT] [COMMAND] <OPTIONS>",
-    );
-
-    help_message.add_section("Commands");
-    help_message.add_command("login", "Login to a taiga instance");
-    help_message.add_command("projects", "Refresh and print the project list");
+fn cli_project_status(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_status_help(project);
+        exit(0);
+    }
 
-    help_message.add_section("Projects");
-    for project in &allowed_projects {
-        help_message.add_command(project, &format!("Run command on {}", project));
+    let mut names = Vec::new();
+    let mut color = None;
+    let mut closed = false;
+    let mut wip_limit = None;
+    let mut move_to = None;
+
+    let mut args_iter = args.iter().skip(1);
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--color" => color = args_iter.next().cloned(),
+            "--closed" => closed = true,
+            "--wip" => {
+                wip_limit = Some(
+                    args_iter
+                        .next()
+                        .and_then(|limit| limit.parse::<i32>().ok())
+                        .unwrap_or_else(|| {
+                            cli_project_status_help(project.clone());
+                            exit(1);
+                        }),
+                )
+            }
+            "--move-to" => move_to = args_iter.next().cloned(),
+            option if option.starts_with("--") => {
+                cli_project_status_help(project);
+                exit(1);
+            }
+            name => names.push(name.to_string()),
+        }
     }
 
-    help_message.add_section("Options");
-    help_message.add_command("--help", "Print the help message and exit");
-    help_message.add_command("--version", "Print the version and exit");
+    let action = match (args.first().map(String::as_str), names.as_slice()) {
+        (Some("add"), [_, ..]) => StatusAction::Add {
+            name: names.join(" "),
+            color,
+            closed,
+            wip_limit,
+        },
+        (Some("rename"), [status, name @ ..]) if !name.is_empty() => StatusAction::Rename {
+            status: status.clone(),
+            name: name.join(" "),
+        },
+        (Some("reorder"), [_, ..]) => StatusAction::Reorder { statuses: names },
+        (Some("close"), [status]) => StatusAction::Close {
+            status: status.clone(),
+            closed: true,
+        },
+        (Some("open"), [status]) => StatusAction::Close {
+            status: status.clone(),
+            closed: false,
+        },
+        (Some("delete"), [status]) => StatusAction::Delete {
+            status: status.clone(),
+            move_to,
+        },
+        _ => {
+            cli_project_status_help(project);
+            exit(1);
+        }
+    };
 
-    help_message.display();
+    TaigaCmd::ProjectStatus(ProjectStatusArgs { project, action })
 }
 
-fn cli_version() {
-    println!("taiga 1.0.0");
+fn cli_project_status_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Manage the statuses of the project",
+        &format!("taiga {} status", project),
+        "[COMMAND] <OPTIONS>",
+    );
+    help_message.add_section("Commands");
+    help_message.add_command("add <NAME>", "Add a status at the end of the board");
+    help_message.add_command("rename <STATUS> <NAME>", "Rename a status");
+    help_message.add_command(
+        "reorder <STATUS> ...",
+        "Put the statuses first on the board, in this order",
+    );
+    help_message.add_command("close <STATUS>", "Make a status close its stories");
+    help_message.add_command("open <STATUS>", "Make a status keep its stories open");
+    help_message.add_command("delete <STATUS>", "Delete a status");
+    help_message.add_section("Options");
+    help_message.add_command("--color <COLOR>", "The color of a new status, like #70728F");
+    help_message.add_command("--closed", "Make a new status close its stories");
+    help_message.add_command("--wip <LIMIT>", "The WIP limit of a new status");
+    help_message.add_command(
+        "--move-to <STATUS>",
+        "Where to move the stories of a deleted status [default: the first other status]",
+    );
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
 }
 
-struct HelpMessage {
-    description: String,
-    command: String,
-    usage: String,
-    sections: Vec<HelpSection>,
-}
+fn cli_project_member(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_member_help(project);
+        exit(0);
+    }
 
-struct HelpSection {
-    title: String,
-    commands: Vec<HelpCommand>,
-}
+    let action = match args
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<&str>>()
+        .as_slice()
+    {
+        ["invite", email, "--role", role] | ["invite", "--role", role, email]
+            if !email.starts_with("--") =>
+        {
+            MemberAction::Invite {
+                email: email.to_string(),
+                role: role.to_string(),
+            }
+        }
+        ["remove", username] if !username.starts_with("--") => MemberAction::Remove {
+            username: username.to_string(),
+        },
+        _ => {
+            cli_project_member_help(project);
+            exit(1);
+        }
+    };
 
-struct HelpCommand {
-    name: String,
-    description: String,
+    TaigaCmd::ProjectMember(ProjectMemberArgs { project, action })
+}
+
+fn cli_project_member_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Manage the members of the project",
+        &format!("taiga {} member", project),
+        "[COMMAND] <OPTIONS>",
+    );
+    help_message.add_section("Commands");
+    help_message.add_command(
+        "invite <EMAIL> --role <ROLE>",
+        "Invite someone to the project with a role",
+    );
+    help_message.add_command("remove <USERNAME>", "Remove a member from the project");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_create(args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) || args.first().map(String::as_str) != Some("new") {
+        cli_project_create_help();
+        exit(if args.contains(&"--help".to_string()) {
+            0
+        } else {
+            1
+        });
+    }
+
+    let mut names = Vec::new();
+    let mut template = "kanban".to_string();
+    let mut description = None;
+
+    let mut args_iter = args.iter().skip(1);
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--template" | "--description" => {
+                let value = args_iter.next().cloned().unwrap_or_else(|| {
+                    cli_project_create_help();
+                    exit(1);
+                });
+                if arg == "--template" {
+                    template = value;
+                } else {
+                    description = Some(value);
+                }
+            }
+            option if option.starts_with("--") => {
+                cli_project_create_help();
+                exit(1);
+            }
+            name => names.push(name.to_string()),
+        }
+    }
+
+    if names.is_empty() {
+        cli_project_create_help();
+        exit(1);
+    }
+
+    TaigaCmd::ProjectNew(ProjectNewArgs {
+        name: names.join(" "),
+        template,
+        description,
+    })
+}
+
+fn cli_project_create_help() {
+    let mut help_message = HelpMessage::new(
+        "Create a new project",
+        "taiga project new",
+        "<NAME> <OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command(
+        "--template <TEMPLATE>",
+        "The template of the project, like kanban or scrum [default: kanban]",
+    );
+    help_message.add_command(
+        "--description <TEXT>",
+        "The description of the project [default: its name]",
+    );
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_recur(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_recur_help(project);
+        exit(0);
+    }
+
+    if !args.is_empty() {
+        cli_project_recur_help(project);
+        exit(1);
+    }
+
+    TaigaCmd::ProjectRecur(ProjectRecurArgs { project })
+}
+
+fn cli_project_recur_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Create the next occurrence of done recurring tasks",
+        &format!("taiga {} recur", project),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_import_stories(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_import_stories_help(project);
+        exit(0);
+    }
+
+    let mut formats = Vec::new();
+    let mut others = Vec::new();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        if arg == "--format" {
+            if let Some(format) = args_iter.next() {
+                formats.push(format.clone());
+            } else {
+                cli_project_import_stories_help(project);
+                exit(1);
+            }
+        } else if arg.starts_with("--") {
+            cli_project_import_stories_help(project);
+            exit(1);
+        } else {
+            others.push(arg.clone());
+        }
+    }
+
+    if others.len() > 1 || formats.len() > 1 {
+        cli_project_import_stories_help(project);
+        exit(1);
+    }
+
+    let path = others.first().filter(|path| *path != "-").cloned();
+
+    let format = match formats.first() {
+        Some(format) => ImportFormat::parse(format).unwrap_or_else(|| {
+            eprintln!("Error, unknown import format '{}'", format);
+            exit(1);
+        }),
+        None => path
+            .as_deref()
+            .map(ImportFormat::from_path)
+            .unwrap_or(ImportFormat::Lines),
+    };
+
+    TaigaCmd::ImportStories(ImportStoriesArgs {
+        project,
+        path,
+        format,
+    })
+}
+
+fn cli_project_import_stories_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Create many tasks from a file or stdin",
+        &format!("taiga {} import-stories", project),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<FILE>", "The file to read from [default: stdin]");
+    help_message.add_section("Options");
+    help_message.add_command(
+        "--format <FORMAT>",
+        "lines, markdown or csv [default: from the file extension]",
+    );
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_export(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_export_help(project);
+        exit(0);
+    }
+
+    let mut formats = Vec::new();
+
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        let format = match arg.as_str() {
+            "--taskwarrior" => "taskwarrior",
+            "--format" => match args_iter.next() {
+                Some(format) => format.as_str(),
+                None => {
+                    cli_project_export_help(project);
+                    exit(1);
+                }
+            },
+            _ => {
+                cli_project_export_help(project);
+                exit(1);
+            }
+        };
+        formats.push(format);
+    }
+
+    let format = match formats.as_slice() {
+        ["taskwarrior"] => ExportFormat::Taskwarrior,
+        ["json"] => ExportFormat::Json,
+        [format] => {
+            eprintln!("Error, unknown export format '{}'", format);
+            exit(1);
+        }
+        _ => {
+            cli_project_export_help(project);
+            exit(1);
+        }
+    };
+
+    TaigaCmd::ProjectExport(ProjectExportArgs { project, format })
+}
+
+fn cli_project_export_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Export the tasks of the project to stdout",
+        &format!("taiga {} export", project),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command(
+        "--format <FORMAT>",
+        "json for a full archive, or taskwarrior",
+    );
+    help_message.add_command("--taskwarrior", "Export as taskwarrior JSON");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_import(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_import_help(project);
+        exit(0);
+    }
+
+    if !args.contains(&"--taskwarrior".to_string()) {
+        cli_project_import_help(project);
+        exit(1);
+    }
+
+    let others: Vec<&String> = args.iter().filter(|arg| *arg != "--taskwarrior").collect();
+    if others.len() > 1 || others.iter().any(|arg| arg.starts_with("--")) {
+        cli_project_import_help(project);
+        exit(1);
+    }
+
+    let path = others
+        .first()
+        .filter(|path| **path != "-")
+        .map(|path| path.to_string());
+
+    TaigaCmd::ProjectImport(ProjectImportArgs { project, path })
+}
+
+fn cli_project_import_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Create or update tasks from `task export`, printing the linked tasks back",
+        &format!("taiga {} import", project),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<FILE>", "The file to read from [default: stdin]");
+    help_message.add_section("Options");
+    help_message.add_command("--taskwarrior", "Import taskwarrior JSON");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_sync(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_sync_help(project);
+        exit(0);
+    }
+
+    let path = match args {
+        [path] if !path.starts_with("--") => path.clone(),
+        _ => {
+            cli_project_sync_help(project);
+            exit(1);
+        }
+    };
+
+    TaigaCmd::ProjectSync(ProjectSyncArgs { project, path })
+}
+
+fn cli_project_sync_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Push the edits of a Markdown board file and pull the remote changes back",
+        &format!("taiga {} sync-file", project),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command(
+        "<FILE>",
+        "The file with a heading per status and an item per task",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_attach(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_attach_help(project, &selector);
+        exit(0);
+    }
+
+    let path = match args {
+        [path] if !path.starts_with("--") => path.clone(),
+        _ => {
+            cli_project_attach_help(project, &selector);
+            exit(1);
+        }
+    };
+
+    TaigaCmd::AttachTask(AttachTaskArgs {
+        project,
+        selector,
+        path,
+    })
+}
+
+fn cli_project_attach_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Upload a file to a task",
+        &format!("taiga {} {} attach", project, selector),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<FILE>", "The file to upload");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_attachments(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_attachments_help(project, &selector);
+        exit(0);
+    }
+
+    if !is_single(&selector) || !args.is_empty() {
+        cli_project_attachments_help(project, &selector);
+        exit(1);
+    }
+
+    TaigaCmd::ListAttachments(ListAttachmentsArgs { project, selector })
+}
+
+fn cli_project_attachments_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "List the files of a single task",
+        &format!("taiga {} {} attachments", project, selector),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_attachment(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_attachment_help(project, &selector);
+        exit(0);
+    }
+
+    if !is_single(&selector) {
+        cli_project_attachment_help(project, &selector);
+        exit(1);
+    }
+
+    let (index, output) = match args {
+        [verb, index] if verb == "get" => (index, None),
+        [verb, index, output] if verb == "get" => (index, Some(output.clone())),
+        _ => {
+            cli_project_attachment_help(project, &selector);
+            exit(1);
+        }
+    };
+
+    let index = match index.parse::<usize>() {
+        Ok(index) if index > 0 => index,
+        _ => {
+            eprintln!("Error, invalid attachment number '{}'", index);
+            exit(1);
+        }
+    };
+
+    TaigaCmd::GetAttachment(GetAttachmentArgs {
+        project,
+        selector,
+        index,
+        output,
+    })
+}
+
+fn cli_project_attachment_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Download a file of a single task",
+        &format!("taiga {} {} attachment get", project, selector),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<N>", "The number of the file in the attachments list");
+    help_message.add_command("<PATH>", "Where to save the file [default: its file name]");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_watch(
+    project: String,
+    selector: TaskSelector,
+    args: &[String],
+    remove: bool,
+) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_watch_help(project, &selector, remove);
+        exit(0);
+    }
+
+    if !args.is_empty() {
+        cli_project_watch_help(project, &selector, remove);
+        exit(1);
+    }
+
+    TaigaCmd::WatchTask(WatchTaskArgs {
+        project,
+        selector,
+        remove,
+    })
+}
+
+fn cli_project_watch_help(project: String, selector: &TaskSelector, remove: bool) {
+    let (description, verb) = if remove {
+        ("Stop watching a task", "unwatch")
+    } else {
+        ("Watch a task to be notified of its changes", "watch")
+    };
+    let mut help_message = HelpMessage::new(
+        description,
+        &format!("taiga {} {} {}", project, selector, verb),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_vote(
+    project: String,
+    selector: TaskSelector,
+    args: &[String],
+    remove: bool,
+) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_vote_help(project, &selector, remove);
+        exit(0);
+    }
+
+    if !args.is_empty() {
+        cli_project_vote_help(project, &selector, remove);
+        exit(1);
+    }
+
+    TaigaCmd::VoteTask(VoteTaskArgs {
+        project,
+        selector,
+        remove,
+    })
+}
+
+fn cli_project_vote_help(project: String, selector: &TaskSelector, remove: bool) {
+    let (description, verb) = if remove {
+        ("Remove your vote from a task", "downvote")
+    } else {
+        ("Vote for a task", "upvote")
+    };
+    let mut help_message = HelpMessage::new(
+        description,
+        &format!("taiga {} {} {}", project, selector, verb),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_show(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_show_help(project, &selector);
+        exit(0);
+    }
+
+    if !is_single(&selector) || !args.is_empty() {
+        cli_project_show_help(project, &selector);
+        exit(1);
+    }
+
+    TaigaCmd::ShowTask(ShowTaskArgs { project, selector })
+}
+
+fn cli_project_comment(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_comment_help(project, &selector);
+        exit(0);
+    }
+
+    if args.is_empty() || args.iter().any(|s| s.starts_with("--")) {
+        cli_project_comment_help(project, &selector);
+        exit(1);
+    }
+
+    TaigaCmd::CommentTask(CommentTaskArgs {
+        project,
+        selector,
+        text: args.join(" "),
+    })
+}
+
+fn cli_project_comment_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Comment on a task",
+        &format!("taiga {} {} comment", project, selector),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<TEXT>", "The comment to add");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_set(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_set_help(project, &selector);
+        exit(0);
+    }
+
+    let values = args
+        .iter()
+        .map(|arg| match arg.split_once('=') {
+            Some((name, value)) if !name.is_empty() => (name.to_string(), value.to_string()),
+            _ => {
+                cli_project_set_help(project.clone(), &selector);
+                exit(1);
+            }
+        })
+        .collect::<Vec<(String, String)>>();
+
+    if values.is_empty() {
+        cli_project_set_help(project, &selector);
+        exit(1);
+    }
+
+    TaigaCmd::SetAttribute(SetAttributeArgs {
+        project,
+        selector,
+        values,
+    })
+}
+
+fn cli_project_set_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Set custom attributes of tasks",
+        &format!("taiga {} {} set", project, selector),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command(
+        "<NAME>=<VALUE>",
+        "The value to give to the attribute - empty to unset it",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_show_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Show the details of a single task",
+        &format!("taiga {} {} show", project, selector),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_branch(
+    project: String,
+    selector: TaskSelector,
+    args: &[String],
+    start: bool,
+) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_branch_help(project, &selector, start);
+        exit(0);
+    }
+
+    if !is_single(&selector) || !args.is_empty() {
+        cli_project_branch_help(project, &selector, start);
+        exit(1);
+    }
+
+    TaigaCmd::BranchTask(BranchTaskArgs {
+        project,
+        selector,
+        start,
+    })
+}
+
+fn cli_project_branch_help(project: String, selector: &TaskSelector, start: bool) {
+    let (description, verb) = if start {
+        (
+            "Start a timer on a task, move it in progress, assign yourself and check out its branch",
+            "start",
+        )
+    } else {
+        ("Create or check out the git branch of a task", "branch")
+    };
+    let mut help_message = HelpMessage::new(
+        description,
+        &format!("taiga {} {} {}", project, selector, verb),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_stop(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_stop_help(project, &selector);
+        exit(0);
+    }
+
+    if !is_single(&selector) || args.iter().any(|s| s.starts_with("--")) {
+        cli_project_stop_help(project, &selector);
+        exit(1);
+    }
+
+    let note = Some(args.join(" ")).filter(|note| !note.is_empty());
+    TaigaCmd::StopTask(StopTaskArgs {
+        project,
+        selector,
+        note,
+    })
+}
+
+fn cli_project_stop_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Stop the timer of a task and log the time spent",
+        &format!("taiga {} {} stop", project, selector),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<NOTE>", "A note for the logged time");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_log(project: String, selector: TaskSelector, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_log_help(project, &selector);
+        exit(0);
+    }
+
+    let minutes = args.first().and_then(|duration| parse_minutes(duration));
+    let minutes = match minutes {
+        Some(minutes) if is_single(&selector) => minutes,
+        _ => {
+            cli_project_log_help(project, &selector);
+            exit(1);
+        }
+    };
+
+    let note = Some(args[1..].join(" ")).filter(|note| !note.is_empty());
+    TaigaCmd::LogTime(LogTimeArgs {
+        project,
+        selector,
+        minutes,
+        note,
+    })
+}
+
+fn cli_project_log_help(project: String, selector: &TaskSelector) {
+    let mut help_message = HelpMessage::new(
+        "Log time spent on a task",
+        &format!("taiga {} {} log", project, selector),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<DURATION>", "The time spent, like 1h30m, 2h or 45m");
+    help_message.add_command("<NOTE>", "A note for the logged time");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_timesheet(project: Option<String>, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_timesheet_help(project);
+        exit(0);
+    }
+
+    let mut week = false;
+    let mut push = None;
+    let mut args_iter = args.iter();
+    while let Some(arg) = args_iter.next() {
+        match arg.as_str() {
+            "--week" => week = true,
+            "--push" => match args_iter.next() {
+                Some(attribute) => push = Some(attribute.clone()),
+                None => {
+                    cli_timesheet_help(project);
+                    exit(1);
+                }
+            },
+            _ => {
+                cli_timesheet_help(project);
+                exit(1);
+            }
+        }
+    }
+
+    TaigaCmd::Timesheet(TimesheetArgs {
+        project,
+        week,
+        push,
+    })
+}
+
+fn cli_timesheet_help(project: Option<String>) {
+    let usage = match &project {
+        Some(project) => format!("taiga {} timesheet", project),
+        None => "taiga timesheet".to_string(),
+    };
+    let mut help_message = HelpMessage::new(
+        "Report the logged time by project and story",
+        &usage,
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--week", "Report the current week instead of today");
+    help_message.add_command(
+        "--push <ATTRIBUTE>",
+        "Save the total time of each story in a custom attribute, replacing its value",
+    );
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn is_single(selector: &TaskSelector) -> bool {
+    match selector {
+        TaskSelector::Ids(ids) => ids.len() == 1,
+        TaskSelector::Reference(_) => true,
+        TaskSelector::Filter(_) => false,
+    }
+}
+
+fn cli_project_install_hook(project: String, args: &[String]) -> TaigaCmd {
+    if !args.is_empty() {
+        cli_project_install_hook_help(project);
+        exit(if args.contains(&"--help".to_string()) {
+            0
+        } else {
+            1
+        });
+    }
+
+    TaigaCmd::InstallHook(InstallHookArgs { project })
+}
+
+fn cli_project_install_hook_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Install a commit-msg hook linking commits to the stories of the project",
+        &format!("taiga {} install-hook", project),
+        "<OPTIONS>",
+    );
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+// The arguments of the commit-msg hook, read before anything that could prompt or fail
+pub fn commit_msg_args() -> Option<CommitMsgArgs> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.as_slice() {
+        [project, verb, path] if verb == "commit-msg" && !path.starts_with("--") => {
+            Some(CommitMsgArgs {
+                project: project.clone(),
+                path: path.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn cli_project_commit_msg(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_commit_msg_help(project);
+        exit(0);
+    }
+
+    let path = match args {
+        [path] => path.clone(),
+        _ => {
+            cli_project_commit_msg_help(project);
+            exit(1);
+        }
+    };
+
+    TaigaCmd::CommitMsg(CommitMsgArgs { project, path })
+}
+
+fn cli_project_commit_msg_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Add the story of the branch to a commit message and apply its #status mentions",
+        &format!("taiga {} commit-msg", project),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<FILE>", "The commit message file given to the hook");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_restore(project: String, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_project_restore_help(project);
+        exit(0);
+    }
+
+    let path = match args {
+        [path] if !path.starts_with("--") => path.clone(),
+        _ => {
+            cli_project_restore_help(project);
+            exit(1);
+        }
+    };
+
+    TaigaCmd::ProjectRestore(ProjectRestoreArgs { project, path })
+}
+
+fn cli_project_restore_help(project: String) {
+    let mut help_message = HelpMessage::new(
+        "Recreate an archive from `export --format json` into an empty project",
+        &format!("taiga {} restore", project),
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<FILE>", "The archive to restore");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_ics(project: Option<String>, args: &[String]) -> TaigaCmd {
+    if args.contains(&"--help".to_string()) {
+        cli_ics_help(project);
+        exit(0);
+    }
+
+    let events = args.contains(&"--events".to_string());
+    let others: Vec<&String> = args.iter().filter(|arg| *arg != "--events").collect();
+    if others.len() > 1 || others.iter().any(|arg| arg.starts_with("--")) {
+        cli_ics_help(project);
+        exit(1);
+    }
+
+    let path = others
+        .first()
+        .filter(|path| **path != "-")
+        .map(|path| path.to_string());
+
+    TaigaCmd::Ics(IcsArgs {
+        project,
+        path,
+        events,
+    })
+}
+
+fn cli_ics_help(project: Option<String>) {
+    let usage = match &project {
+        Some(project) => format!("taiga {} ics", project),
+        None => "taiga ics".to_string(),
+    };
+    let mut help_message = HelpMessage::new(
+        "Export the tasks with a due date as an iCalendar file",
+        &usage,
+        "<ARGS> <OPTIONS>",
+    );
+    help_message.add_section("Arguments");
+    help_message.add_command("<FILE>", "The file to write to [default: stdout]");
+    help_message.add_section("Options");
+    help_message.add_command("--events", "Write all day events instead of todos");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_project_burndown(project: String, _args: &[String]) -> TaigaCmd {
+    // TODO: implement help
+    TaigaCmd::ProjectBurndown(ProjectBurndownArgs { project })
+}
+
+fn cli_help_project(project: String) {
+    let mut help_message = HelpMessage::new(
+        &format!("Run command on {}", project),
+        &format!("taiga {}", project),
+        "[COMMAND] <OPTIONS>",
+    );
+    help_message.add_section("Commands");
+    help_message.add_command("new", "Create a new task");
+    help_message.add_command("move <CARD-ID>", "Move a task to a status");
+    help_message.add_command("done <CARD-ID>", "Declare a task as done");
+    help_message.add_command(
+        "reopen <CARD-ID>",
+        "Move a closed task back to an open status",
+    );
+    help_message.add_command("rename <CARD-ID>", "Rename a task");
+    help_message.add_command("assign <CARD-ID>", "Assign a task");
+    help_message.add_command("due <CARD-ID>", "Set due date for a task");
+    help_message.add_command("team <CARD-ID>", "Toggle team requirement for a task");
+    help_message.add_command("client <CARD-ID>", "Toggle client requirement for a task");
+    help_message.add_command("block <CARD-ID>", "Toggle block for a task");
+    help_message.add_command("modify <CARD-ID>", "Modify a task");
+    help_message.add_command("delete <CARD-ID>", "Delete a task");
+    help_message.add_command("<CARD-ID> attach <FILE>", "Upload a file to a task");
+    help_message.add_command("<CARD-ID> attachments", "List the files of a task");
+    help_message.add_command("<CARD-ID> attachment get <N>", "Download a file of a task");
+    help_message.add_command("<CARD-ID> show", "Show the details of a task");
+    help_message.add_command("<CARD-ID> comment <TEXT>", "Comment on a task");
+    help_message.add_command(
+        "<CARD-ID> set <NAME>=<VALUE>",
+        "Set custom attributes of tasks",
+    );
+    help_message.add_command("<CARD-ID> branch", "Check out the git branch of a task");
+    help_message.add_command("<CARD-ID> start", "Start working and a timer on a task");
+    help_message.add_command("<CARD-ID> stop", "Stop the timer of a task");
+    help_message.add_command("<CARD-ID> log <DURATION>", "Log time spent on a task");
+    help_message.add_command("<CARD-ID> watch", "Watch or unwatch a task");
+    help_message.add_command("<CARD-ID> upvote", "Upvote or downvote a task");
+    help_message.add_command("search", "Search for tasks that fit requirements");
+    help_message.add_command("users", "List users for the project");
+    help_message.add_command("board", "Show the kanban board of the project");
+    help_message.add_command("status", "Add, rename, reorder, close or delete statuses");
+    help_message.add_command("member", "Invite or remove members");
+    help_message.add_command("recur", "Create the next occurrence of recurring tasks");
+    help_message.add_command("import-stories", "Create many tasks from a file or stdin");
+    help_message.add_command("export", "Export the tasks of the project");
+    help_message.add_command("import", "Create or update tasks from an export");
+    help_message.add_command("sync-file <FILE>", "Sync the tasks with a Markdown file");
+    help_message.add_command("ics", "Export due dates as an iCalendar file");
+    help_message.add_command("restore <FILE>", "Recreate an exported archive");
+    help_message.add_command("install-hook", "Install a git hook referencing stories");
+    help_message.add_command("timesheet", "Report the time logged on the project");
+    help_message.add_section("Selection");
+    help_message.add_command("<IDS> <COMMAND>", "Run a command on card ids like 3,5-8");
+    help_message.add_command(
+        "<MODIFIERS> [--] <COMMAND>",
+        "Run a command on the tasks matching search modifiers, show, set and log need the --",
+    );
+    help_message.add_command("burndown", "List statistics for the project");
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.display();
+}
+
+fn cli_help(allowed_projects: Vec<String>, config: &TaigaConfig) {
+    let mut help_message = HelpMessage::new(
+        "Cli tool for taiga.io",
+        "taiga",
+        "[PROJECT] [COMMAND] <OPTIONS>",
+    );
+
+    help_message.add_section("Commands");
+    help_message.add_command("login", "Login to a taiga instance");
+    help_message.add_command("projects", "Refresh and print the project list");
+    help_message.add_command(
+        "ics",
+        "Export due dates of all projects as an iCalendar file",
+    );
+    help_message.add_command("timesheet", "Report the time logged on all projects");
+    help_message.add_command(
+        ". <COMMAND>",
+        "Run a command on the project of the repository and the story of the branch",
+    );
+    help_message.add_command(
+        "webhook serve",
+        "Receive webhooks to keep the cache current",
+    );
+    help_message.add_command("project new <NAME>", "Create a new project");
+
+    help_message.add_section("Projects");
+    for project in &allowed_projects {
+        let description = if config.default_project.as_ref() == Some(project) {
+            format!("Run command on {} [default]", project)
+        } else {
+            format!("Run command on {}", project)
+        };
+        help_message.add_command(project, &description);
+    }
+
+    if !config.aliases.is_empty() {
+        help_message.add_section("Aliases");
+        let mut aliases = config.aliases.iter().collect::<Vec<(&String, &String)>>();
+        aliases.sort();
+        for (alias, project) in aliases {
+            help_message.add_command(alias, &format!("Alias for {}", project));
+        }
+    }
+
+    help_message.add_section("Options");
+    help_message.add_command("--help", "Print the help message and exit");
+    help_message.add_command("--version", "Print the version and exit");
+
+    help_message.display();
+}
+
+fn cli_version() {
+    println!("taiga 1.0.0");
+}
+
+struct HelpMessage {
+    description: String,
+    command: String,
+    usage: String,
+    sections: Vec<HelpSection>,
+}
+
+struct HelpSection {
+    title: String,
+    commands: Vec<HelpCommand>,
+}
+
+struct HelpCommand {
+    name: String,
+    description: String,
 }
 
 impl HelpMessage {
@@ -1587,3 +3675,309 @@ impl HelpMessage {
         table.printstd();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_id_lists_and_ranges() {
+        assert_eq!(parse_ids("3"), Some(vec![(3, 3)]));
+        assert_eq!(parse_ids("3,5-8"), Some(vec![(3, 3), (5, 8)]));
+        assert_eq!(parse_ids("0"), None);
+        assert_eq!(parse_ids("5-3"), None);
+        assert_eq!(parse_ids("3,"), None);
+        assert_eq!(parse_ids("due:fri"), None);
+    }
+
+    #[test]
+    fn expands_distinct_ids_in_order() {
+        let ranges = parse_ids("3,5,3,4-6").unwrap();
+        assert_eq!(expand_ids(&ranges, 10), Some(vec![3, 5, 4, 6]));
+    }
+
+    #[test]
+    fn refuses_ids_past_the_cached_tasks() {
+        let ranges = parse_ids("1-1000000000").unwrap();
+        assert_eq!(expand_ids(&ranges, 20), None);
+        assert_eq!(expand_ids(&parse_ids("21").unwrap(), 20), None);
+        assert_eq!(expand_ids(&parse_ids("20").unwrap(), 20), Some(vec![20]));
+    }
+
+    // Parse the assignee modifiers of a modify command line
+    fn assign(line: &str) -> Option<AssignChange> {
+        let words = args(line);
+        let assigned: Vec<&String> = words.iter().filter(|word| word.starts_with('@')).collect();
+        let changes: Vec<&str> = words
+            .iter()
+            .filter_map(|word| word.strip_prefix("assign:"))
+            .collect();
+        parse_assign(&assigned, &changes)
+    }
+
+    #[test]
+    fn parses_assignee_changes() {
+        let change = assign("assign:=ann,bob").unwrap();
+        assert_eq!(
+            change.replace,
+            Some(vec!["ann".to_string(), "bob".to_string()])
+        );
+        assert!(change.add.is_empty() && change.remove.is_empty());
+
+        let change = assign("assign:+cat assign:-ann").unwrap();
+        assert_eq!(change.replace, None);
+        assert_eq!(change.add, vec!["cat"]);
+        assert_eq!(change.remove, vec!["ann"]);
+
+        // without a sign the users are added
+        assert_eq!(assign("assign:cat,dan").unwrap().add, vec!["cat", "dan"]);
+
+        // alone it clears the assignees
+        assert_eq!(assign("assign:").unwrap().replace, Some(Vec::new()));
+    }
+
+    #[test]
+    fn mixes_users_with_assignee_changes() {
+        let change = assign("@ann assign:=bob assign:+cat").unwrap();
+        assert_eq!(change.replace, Some(vec!["bob".to_string()]));
+        assert_eq!(change.add, vec!["ann", "cat"]);
+
+        match cli_project_modify(
+            "taiga".to_string(),
+            TaskSelector::Ids(vec![1]),
+            &args("@ann assign:-bob"),
+        ) {
+            TaigaCmd::ModifyTask(args) => {
+                let change = args.assign.unwrap();
+                assert_eq!(change.add, vec!["ann"]);
+                assert_eq!(change.remove, vec!["bob"]);
+            }
+            cmd => panic!("unexpected command {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_assignee_changes() {
+        assert!(assign("assign:=ann assign:=bob").is_none());
+        assert!(assign("assign: assign:=bob").is_none());
+        assert!(assign("assign:+").is_none());
+        assert!(assign("assign:-,").is_none());
+        assert!(assign("@").is_none());
+    }
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn status_action(line: &str) -> StatusAction {
+        match cli_project_status("taiga".to_string(), &args(line)) {
+            TaigaCmd::ProjectStatus(args) => args.action,
+            cmd => panic!("unexpected command {:?}", cmd),
+        }
+    }
+
+    fn member_action(line: &str) -> MemberAction {
+        match cli_project_member("taiga".to_string(), &args(line)) {
+            TaigaCmd::ProjectMember(args) => args.action,
+            cmd => panic!("unexpected command {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn parses_status_commands() {
+        match status_action("add In review --wip 3 --closed") {
+            StatusAction::Add {
+                name,
+                color,
+                closed,
+                wip_limit,
+            } => {
+                assert_eq!(name, "In review");
+                assert_eq!(color, None);
+                assert!(closed);
+                assert_eq!(wip_limit, Some(3));
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+        match status_action("rename review Peer review") {
+            StatusAction::Rename { status, name } => {
+                assert_eq!(status, "review");
+                assert_eq!(name, "Peer review");
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+        match status_action("reorder done new") {
+            StatusAction::Reorder { statuses } => assert_eq!(statuses, vec!["done", "new"]),
+            action => panic!("unexpected action {:?}", action),
+        }
+        match status_action("open done") {
+            StatusAction::Close { status, closed } => {
+                assert_eq!(status, "done");
+                assert!(!closed);
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+        match status_action("delete review --move-to done") {
+            StatusAction::Delete { status, move_to } => {
+                assert_eq!(status, "review");
+                assert_eq!(move_to.as_deref(), Some("done"));
+            }
+            action => panic!("unexpected action {:?}", action),
+        }
+    }
+
+    #[test]
+    fn parses_member_commands() {
+        for line in [
+            "invite ann@example.com --role Back",
+            "invite --role Back ann@example.com",
+        ] {
+            match member_action(line) {
+                MemberAction::Invite { email, role } => {
+                    assert_eq!(email, "ann@example.com");
+                    assert_eq!(role, "Back");
+                }
+                action => panic!("unexpected action {:?}", action),
+            }
+        }
+        match member_action("remove ann") {
+            MemberAction::Remove { username } => assert_eq!(username, "ann"),
+            action => panic!("unexpected action {:?}", action),
+        }
+    }
+
+    #[test]
+    fn parses_closed_filters() {
+        let filter = parse_filter("taiga".to_string(), &args("status:new"));
+        assert!(!filter.closed);
+        assert_eq!(filter.include_statuses, vec!["new"]);
+
+        let filter = parse_filter("taiga".to_string(), &args("+closed"));
+        assert!(filter.closed);
+        assert!(filter.include_statuses.is_empty());
+
+        // status:all is not a status of its own
+        let filter = parse_filter("taiga".to_string(), &args("status:all"));
+        assert!(filter.closed);
+        assert!(filter.include_statuses.is_empty());
+
+        let filter = parse_filter("taiga".to_string(), &args("status:all status:review"));
+        assert!(filter.closed);
+        assert_eq!(filter.include_statuses, vec!["review"]);
+    }
+
+    #[test]
+    fn finds_the_command_after_modifiers() {
+        assert_eq!(
+            filter_command(&args("status:review done")),
+            Some(Ok((1, 1)))
+        );
+        assert_eq!(filter_command(&args("@me -- log 1h")), Some(Ok((1, 2))));
+        assert_eq!(filter_command(&args("-- show")), Some(Ok((0, 1))));
+    }
+
+    #[test]
+    fn refuses_commands_that_read_as_search_words() {
+        assert_eq!(filter_command(&args("@me log")), Some(Err("log")));
+        assert_eq!(filter_command(&args("+bug show")), Some(Err("show")));
+        assert_eq!(filter_command(&args("set")), Some(Err("set")));
+    }
+
+    #[test]
+    fn parses_watch_intervals() {
+        assert_eq!(parse_seconds("45"), Some(45));
+        assert_eq!(parse_seconds("2m"), Some(120));
+        assert_eq!(parse_seconds("1h"), Some(3600));
+        assert_eq!(parse_seconds("0"), None);
+        assert_eq!(parse_seconds("5d"), None);
+        assert_eq!(parse_seconds("status:new"), None);
+    }
+
+    #[test]
+    fn attachments_take_the_branch_story() {
+        let cmd = cli_project_attachments("web".to_string(), TaskSelector::Reference(12), &[]);
+        assert!(matches!(
+            cmd,
+            TaigaCmd::ListAttachments(ListAttachmentsArgs {
+                selector: TaskSelector::Reference(12),
+                ..
+            })
+        ));
+
+        let cmd = cli_project_attachment(
+            "web".to_string(),
+            TaskSelector::Reference(12),
+            &args("get 2 notes.txt"),
+        );
+        assert!(matches!(
+            cmd,
+            TaigaCmd::GetAttachment(GetAttachmentArgs {
+                selector: TaskSelector::Reference(12),
+                index: 2,
+                ..
+            })
+        ));
+    }
+
+    fn new_task(line: &str) -> NewTaskArgs {
+        let config: TaigaConfig = toml::from_str(
+            r#"
+            [templates.bug]
+            prefix = "[bug] "
+            description = "Steps"
+            tags = ["bug"]
+            status = "new"
+            assign = ["me"]
+            client = true
+            points = 3
+            "#,
+        )
+        .unwrap();
+
+        match cli_project_new("web".to_string(), &args(line), &config) {
+            TaigaCmd::NewTask(args) => args,
+            cmd => panic!("unexpected command {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn new_tasks_take_the_template_values() {
+        let task = new_task("--template bug Login fails");
+        assert_eq!(task.name, "[bug] Login fails");
+        assert_eq!(task.description.as_deref(), Some("Steps"));
+        assert_eq!(task.tags, vec!["bug"]);
+        assert_eq!(task.status.as_deref(), Some("new"));
+        assert_eq!(task.assign, vec!["me"]);
+        assert!(task.client && !task.team);
+        assert_eq!(task.points, Some(3.0));
+    }
+
+    #[test]
+    fn modifiers_override_the_template() {
+        let task = new_task("--template bug status:triage @alice -client desc:Crash tags:ui,login points:5 Login fails");
+        assert_eq!(task.status.as_deref(), Some("triage"));
+        assert_eq!(task.assign, vec!["alice"]);
+        assert!(!task.client);
+        assert_eq!(task.description.as_deref(), Some("Crash"));
+        assert_eq!(task.tags, vec!["ui", "login"]);
+        assert_eq!(task.points, Some(5.0));
+
+        let task = new_task("--template bug desc: tags: Login fails");
+        assert_eq!(task.description, None);
+        assert!(task.tags.is_empty());
+    }
+
+    #[test]
+    fn new_tasks_without_template() {
+        let task = new_task("Write docs");
+        assert_eq!(task.name, "Write docs");
+        assert!(task.description.is_none() && task.tags.is_empty() && task.points.is_none());
+    }
+
+    #[test]
+    fn leaves_searches_alone() {
+        assert_eq!(filter_command(&args("login page")), None);
+        assert_eq!(filter_command(&args("fix login done")), None);
+        assert_eq!(filter_command(&args("@me -- login")), None);
+    }
+}
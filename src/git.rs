@@ -0,0 +1,182 @@
+use anyhow::{anyhow, Context, Result};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::utils::slug;
+
+const BRANCH_SLUG_LENGTH: usize = 50;
+
+lazy_static! {
+    static ref BRANCH_REGEX: Regex = Regex::new(r"(?:^|/)us-(\d+)(?:-|$)").unwrap();
+    static ref COMMIT_REGEX: Regex = Regex::new(r"\bTG-(\d+)(?:[ \t]+#([\w-]+))?").unwrap();
+}
+
+// Name a branch after a story, like us-142-fix-login
+pub fn branch_name(reference: i32, name: &str) -> String {
+    let words: Vec<String> = slug(name.to_string())
+        .split('-')
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect();
+
+    let mut name = format!("us-{}", reference);
+    let mut length = 0;
+    for word in words {
+        length += word.chars().count() + 1;
+        if length > BRANCH_SLUG_LENGTH + 1 {
+            break;
+        }
+        name.push('-');
+        name.push_str(&word);
+    }
+
+    name
+}
+
+// The story reference of a branch named by `branch_name`
+pub fn branch_reference(branch: &str) -> Option<i32> {
+    BRANCH_REGEX
+        .captures(branch)
+        .and_then(|caps| caps[1].parse().ok())
+}
+
+// The `TG-<ref>` mentions of a commit message, with the status of `TG-<ref> #status`
+pub fn commit_references(message: &str) -> Vec<(i32, Option<String>)> {
+    COMMIT_REGEX
+        .captures_iter(message)
+        .filter_map(|caps| {
+            let reference = caps[1].parse().ok()?;
+            Some((
+                reference,
+                caps.get(2).map(|status| status.as_str().to_string()),
+            ))
+        })
+        .collect()
+}
+
+// The project of the repository, from a .taiga file in the directory or one of its parents,
+// or else from the taiga.project git config key
+pub fn repository_project() -> Option<String> {
+    let mut dir = env::current_dir().ok();
+    while let Some(path) = dir {
+        let file = path.join(".taiga");
+        if file.is_file() {
+            return fs::read_to_string(file).ok().and_then(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(String::from)
+            });
+        }
+        dir = path.parent().map(PathBuf::from);
+    }
+
+    git(&["config", "--get", "taiga.project"])
+        .ok()
+        .filter(|project| !project.is_empty())
+}
+
+pub fn in_repository() -> bool {
+    git(&["rev-parse", "--is-inside-work-tree"]).is_ok()
+}
+
+pub fn current_branch() -> Result<String> {
+    git(&["symbolic-ref", "--short", "HEAD"])
+}
+
+// Check out a branch, creating it from HEAD when it does not exist yet
+pub fn checkout_branch(name: &str) -> Result<bool> {
+    let exists = git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("refs/heads/{}", name),
+    ])
+    .is_ok();
+
+    if exists {
+        git(&["checkout", name])?;
+    } else {
+        git(&["checkout", "-b", name])?;
+    }
+
+    Ok(!exists)
+}
+
+pub fn hooks_dir() -> Result<PathBuf> {
+    git(&["rev-parse", "--git-path", "hooks"]).map(PathBuf::from)
+}
+
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Could not run git")?;
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("git {} failed: {}", args[0], message));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_branches_after_stories() {
+        assert_eq!(
+            branch_name(142, "Fix the login page!"),
+            "us-142-fix-the-login-page"
+        );
+        assert_eq!(branch_name(7, "  "), "us-7");
+    }
+
+    #[test]
+    fn shortens_long_branch_names_on_words() {
+        let name = branch_name(1, &"word ".repeat(30));
+        let slug = name.strip_prefix("us-1-").unwrap();
+
+        assert!(slug.len() <= BRANCH_SLUG_LENGTH);
+        assert!(slug.split('-').all(|word| word == "word"));
+    }
+
+    #[test]
+    fn reads_references_of_branches() {
+        assert_eq!(branch_reference("us-142-fix-login"), Some(142));
+        assert_eq!(branch_reference("us-142"), Some(142));
+        assert_eq!(branch_reference("feature/us-9-footer"), Some(9));
+        assert_eq!(branch_reference(&branch_name(31, "Round trip")), Some(31));
+        assert_eq!(branch_reference("bus-142-fix"), None);
+        assert_eq!(branch_reference("us-142fix"), None);
+        assert_eq!(branch_reference("main"), None);
+    }
+
+    #[test]
+    fn reads_commit_references_and_statuses() {
+        let message = "Fix login TG-12 #done\n\nSee TG-3 and TG-40 #in-progress, not XTG-5";
+
+        assert_eq!(
+            commit_references(message),
+            vec![
+                (12, Some("done".to_string())),
+                (3, None),
+                (40, Some("in-progress".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_statuses_not_following_a_reference() {
+        assert_eq!(commit_references("TG-12\n#done"), vec![(12, None)]);
+        assert_eq!(commit_references("TG-12 done #wip"), vec![(12, None)]);
+        assert!(commit_references("#done TG-").is_empty());
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::taiga::{TaigaConfig, TaigaTask};
+
+// Executables of the hooks directory whose name starts with the event, in name order
+pub fn find_hooks(event: &str) -> Vec<PathBuf> {
+    let dir = match TaigaConfig::hooks_dir() {
+        Some(dir) => dir,
+        None => return Vec::new(),
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut hooks: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(event))
+        .map(|entry| entry.path())
+        .filter(|path| is_executable(path))
+        .collect();
+    hooks.sort();
+
+    hooks
+}
+
+pub fn has_hooks(command: &str) -> bool {
+    !find_hooks(&format!("pre-{}", command)).is_empty()
+        || !find_hooks(&format!("post-{}", command)).is_empty()
+}
+
+// Pass a change through the pre hooks of a command, each receiving the old and new task as a
+// JSON line. A hook vetoes the change by exiting non-zero and rewrites it by printing a task,
+// any other line it prints is shown as feedback.
+pub fn run_pre_hooks(
+    command: &str,
+    old: Option<&TaigaTask>,
+    new: Option<TaigaTask>,
+) -> Result<Option<TaigaTask>> {
+    let mut new = new;
+
+    for hook in find_hooks(&format!("pre-{}", command)) {
+        let output = run_hook(&hook, &hook_input(old, new.as_ref())?)?;
+
+        let mut rewritten = None;
+        for line in output
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+        {
+            if line.starts_with('{') {
+                rewritten = Some(line);
+            } else {
+                println!("{}", line);
+            }
+        }
+
+        if let (Some(line), Some(_)) = (rewritten, &new) {
+            let name = hook.file_name().unwrap_or_default().to_string_lossy();
+            new = Some(
+                serde_json::from_str(line)
+                    .with_context(|| format!("hook {} printed an invalid task", name))?,
+            );
+        }
+    }
+
+    Ok(new)
+}
+
+// Run the post hooks of a command, their failures do not undo the change
+pub fn run_post_hooks(command: &str, old: Option<&TaigaTask>, new: Option<&TaigaTask>) {
+    for hook in find_hooks(&format!("post-{}", command)) {
+        match hook_input(old, new).and_then(|input| run_hook(&hook, &input)) {
+            Ok(output) => print!("{}", output),
+            Err(err) => eprintln!("Warning, {}", err),
+        }
+    }
+}
+
+fn hook_input(old: Option<&TaigaTask>, new: Option<&TaigaTask>) -> Result<String> {
+    Ok(format!(
+        "{}\n{}\n",
+        serde_json::to_string(&old)?,
+        serde_json::to_string(&new)?
+    ))
+}
+
+// Run a hook with the input on stdin and return its output, failing on a non-zero exit
+pub fn run_hook(path: &Path, input: &str) -> Result<String> {
+    let mut child = Command::new(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Could not run hook {}", path.display()))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // a hook that does not read its input closes the pipe early
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("Could not run hook {}", path.display()))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let message = if message.is_empty() {
+            stdout.trim().to_string()
+        } else {
+            message
+        };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        return Err(if message.is_empty() {
+            anyhow!("hook {} failed", name)
+        } else {
+            anyhow!("hook {} failed: {}", name, message)
+        });
+    }
+
+    Ok(stdout)
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -0,0 +1,192 @@
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+#[derive(Debug, Clone)]
+pub struct CalendarEntry {
+    pub uid: String,
+    pub summary: String,
+    pub due: NaiveDate,
+    pub project: String,
+    pub status: String,
+    pub assigned: Vec<String>,
+    pub url: String,
+    pub closed: bool,
+}
+
+// Render the entries as an RFC 5545 calendar, as todos or as all day events
+pub fn render_calendar(entries: &[CalendarEntry], events: bool, now: DateTime<Utc>) -> String {
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//taiga-cli//taiga-cli//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for entry in entries {
+        let component = if events { "VEVENT" } else { "VTODO" };
+        let due = entry.due.format("%Y%m%d").to_string();
+
+        let mut description = format!("Status: {}", entry.status);
+        if !entry.assigned.is_empty() {
+            description.push_str(&format!("\nAssigned: {}", entry.assigned.join(", ")));
+        }
+
+        lines.push(format!("BEGIN:{}", component));
+        lines.push(format!("UID:{}", entry.uid));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        // events have no completion state, so it is carried by the summary
+        let summary = if events && entry.closed {
+            format!("[done] {}", entry.summary)
+        } else {
+            entry.summary.clone()
+        };
+        lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+        lines.push(format!(
+            "CATEGORIES:{},{}",
+            escape_text(&entry.project),
+            escape_text(&entry.status)
+        ));
+        lines.push(format!("URL:{}", entry.url));
+
+        if events {
+            let end = entry
+                .due
+                .checked_add_days(Days::new(1))
+                .unwrap_or(entry.due);
+            lines.push(format!("DTSTART;VALUE=DATE:{}", due));
+            lines.push(format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")));
+            lines.push("TRANSP:TRANSPARENT".to_string());
+            lines.push("STATUS:CONFIRMED".to_string());
+        } else {
+            lines.push(format!("DUE;VALUE=DATE:{}", due));
+            if entry.closed {
+                lines.push("STATUS:COMPLETED".to_string());
+                lines.push("PERCENT-COMPLETE:100".to_string());
+                lines.push(format!("COMPLETED:{}", stamp));
+            } else {
+                lines.push("STATUS:NEEDS-ACTION".to_string());
+            }
+        }
+
+        lines.push(format!("END:{}", component));
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// Fold a content line at 75 octets without splitting a character, and end it with CRLF
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(closed: bool) -> CalendarEntry {
+        CalendarEntry {
+            uid: "story-12@taiga".to_string(),
+            summary: "Fix login, again".to_string(),
+            due: NaiveDate::from_ymd_opt(2026, 10, 31).unwrap(),
+            project: "Web".to_string(),
+            status: "in-progress".to_string(),
+            assigned: vec!["alice".to_string(), "bob".to_string()],
+            url: "https://tree.taiga.io/project/web/us/12".to_string(),
+            closed,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 18, 9, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), r"a\,b\;c\\d\ne");
+    }
+
+    #[test]
+    fn keeps_short_lines_whole() {
+        assert_eq!(fold_line("SUMMARY:Fix login"), "SUMMARY:Fix login\r\n");
+        let exact = "X".repeat(75);
+        assert_eq!(fold_line(&exact), format!("{}\r\n", exact));
+    }
+
+    #[test]
+    fn folds_lines_at_75_octets() {
+        let line = "X".repeat(200);
+        let folded = fold_line(&line);
+        let parts: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0].len(), 75);
+        assert_eq!(parts[1].len(), 75);
+        assert!(parts[1].starts_with(' '));
+        assert!(parts.iter().all(|part| part.len() <= 75));
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+
+    #[test]
+    fn folds_without_splitting_characters() {
+        let line = format!("{}é", "X".repeat(74));
+        let folded = fold_line(&line);
+
+        assert_eq!(folded, format!("{}\r\n é\r\n", "X".repeat(74)));
+        assert!(folded.split("\r\n").all(|part| part.len() <= 75));
+    }
+
+    #[test]
+    fn renders_todos() {
+        let calendar = render_calendar(&[entry(false), entry(true)], false, now());
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VTODO\r\n").count(), 2);
+        assert!(calendar.contains("SUMMARY:Fix login\\, again\r\n"));
+        assert!(calendar.contains("DESCRIPTION:Status: in-progress\\nAssigned: alice\\, bob\r\n"));
+        assert!(calendar.contains("DUE;VALUE=DATE:20261031\r\n"));
+        assert!(calendar.contains("DTSTAMP:20261018T093000Z\r\n"));
+        assert!(calendar.contains("STATUS:NEEDS-ACTION\r\n"));
+        assert!(calendar.contains("STATUS:COMPLETED\r\nPERCENT-COMPLETE:100\r\n"));
+    }
+
+    #[test]
+    fn renders_all_day_events() {
+        let calendar = render_calendar(&[entry(true)], true, now());
+
+        assert!(calendar.contains("BEGIN:VEVENT\r\n"));
+        assert!(calendar.contains("SUMMARY:[done] Fix login\\, again\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20261031\r\n"));
+        assert!(calendar.contains("DTEND;VALUE=DATE:20261101\r\n"));
+        assert!(!calendar.contains("VTODO"));
+    }
+}
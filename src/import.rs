@@ -0,0 +1,340 @@
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy)]
+pub enum ImportFormat {
+    Lines,
+    Markdown,
+    Csv,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportStory {
+    pub line: usize,
+    pub subject: String,
+    pub status: Option<String>,
+    pub assign: Vec<String>,
+    pub due: Option<String>,
+    pub tags: Vec<String>,
+    pub done: bool,
+}
+
+impl ImportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "lines" | "txt" => Some(ImportFormat::Lines),
+            "markdown" | "md" => Some(ImportFormat::Markdown),
+            "csv" => Some(ImportFormat::Csv),
+            _ => None,
+        }
+    }
+
+    // Guess the format from the file extension, defaulting to one story per line
+    pub fn from_path(path: &str) -> Self {
+        Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| Self::parse(&ext.to_lowercase()))
+            .unwrap_or(ImportFormat::Lines)
+    }
+}
+
+// Parse the input into stories, keeping the line number of every story and every error
+pub fn parse_stories(
+    input: &str,
+    format: ImportFormat,
+) -> Vec<Result<ImportStory, (usize, String)>> {
+    match format {
+        ImportFormat::Lines => parse_lines(input),
+        ImportFormat::Markdown => parse_markdown(input),
+        ImportFormat::Csv => parse_csv(input),
+    }
+}
+
+fn parse_lines(input: &str) -> Vec<Result<ImportStory, (usize, String)>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| Ok(ImportStory::new(i + 1, line.trim())))
+        .collect()
+}
+
+fn parse_markdown(input: &str) -> Vec<Result<ImportStory, (usize, String)>> {
+    input
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.trim();
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))?
+                .trim_start();
+
+            let (done, subject) = if let Some(subject) = item.strip_prefix("[ ]") {
+                (false, subject)
+            } else if let Some(subject) = item
+                .strip_prefix("[x]")
+                .or_else(|| item.strip_prefix("[X]"))
+            {
+                (true, subject)
+            } else {
+                return None;
+            };
+
+            let subject = subject.trim();
+            if subject.is_empty() {
+                return Some(Err((i + 1, "empty checklist item".to_string())));
+            }
+
+            let mut story = ImportStory::new(i + 1, subject);
+            story.done = done;
+            Some(Ok(story))
+        })
+        .collect()
+}
+
+fn parse_csv(input: &str) -> Vec<Result<ImportStory, (usize, String)>> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let header = match lines.next() {
+        Some((_, header)) => split_csv_line(header)
+            .iter()
+            .map(|column| column.trim().to_lowercase())
+            .collect::<Vec<String>>(),
+        None => return Vec::new(),
+    };
+
+    let column = |name: &str| header.iter().position(|column| column == name);
+    let subject_column = match column("subject") {
+        Some(index) => index,
+        None => return vec![Err((1, "missing subject column".to_string()))],
+    };
+    let status_column = column("status");
+    let assignee_column = column("assignee");
+    let due_column = column("due");
+    let tags_column = column("tags");
+
+    lines
+        .map(|(i, line)| {
+            let fields = split_csv_line(line);
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| fields.get(index))
+                    .map(|field| field.trim().to_string())
+                    .filter(|field| !field.is_empty())
+            };
+            let list = |index: Option<usize>| {
+                field(index)
+                    .map(|field| {
+                        field
+                            .split([';', ','])
+                            .map(|item| item.trim().to_string())
+                            .filter(|item| !item.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+
+            let subject = field(Some(subject_column))
+                .ok_or_else(|| (i + 1, "missing subject".to_string()))?;
+
+            Ok(ImportStory {
+                line: i + 1,
+                subject,
+                status: field(status_column),
+                assign: list(assignee_column),
+                due: field(due_column),
+                tags: list(tags_column),
+                done: false,
+            })
+        })
+        .collect()
+}
+
+// Split a csv line on commas, honoring double quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+impl ImportStory {
+    fn new(line: usize, subject: &str) -> Self {
+        ImportStory {
+            line,
+            subject: subject.to_string(),
+            status: None,
+            assign: Vec::new(),
+            due: None,
+            tags: Vec::new(),
+            done: false,
+        }
+    }
+}
+
+// Pair each subject sent to a bulk creation with the story created for it, by subject since
+// the server does not promise to keep the order, also returning the stories left unpaired
+pub fn match_created<T>(
+    subjects: &[String],
+    created: Vec<T>,
+    name: impl Fn(&T) -> &str,
+) -> (Vec<Option<T>>, Vec<T>) {
+    let mut left: Vec<Option<T>> = created.into_iter().map(Some).collect();
+
+    let matched = subjects
+        .iter()
+        .map(|subject| {
+            let position = left.iter().position(|story| {
+                story
+                    .as_ref()
+                    .is_some_and(|story| name(story).trim() == subject.trim())
+            })?;
+            left[position].take()
+        })
+        .collect();
+
+    (matched, left.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_created_stories_by_subject() {
+        let subjects = vec![
+            "Fix login".to_string(),
+            "Write docs".to_string(),
+            "Fix login".to_string(),
+        ];
+        let created = vec!["Write docs", "Fix login ", "Fix login", "Extra"];
+
+        let (matched, left) = match_created(&subjects, created, |name| name);
+        assert_eq!(
+            matched,
+            vec![Some("Fix login "), Some("Write docs"), Some("Fix login")]
+        );
+        assert_eq!(left, vec!["Extra"]);
+    }
+
+    #[test]
+    fn reports_dropped_stories() {
+        let subjects = vec!["Fix login".to_string(), "Write docs".to_string()];
+
+        let (matched, left) = match_created(&subjects, vec!["Write docs"], |name| name);
+        assert_eq!(matched, vec![None, Some("Write docs")]);
+        assert!(left.is_empty());
+    }
+
+    fn stories(input: &str, format: ImportFormat) -> Vec<ImportStory> {
+        parse_stories(input, format)
+            .into_iter()
+            .map(|story| story.expect("Could not parse story"))
+            .collect()
+    }
+
+    #[test]
+    fn guesses_format_from_extension() {
+        assert!(matches!(
+            ImportFormat::from_path("todo.MD"),
+            ImportFormat::Markdown
+        ));
+        assert!(matches!(
+            ImportFormat::from_path("backlog.csv"),
+            ImportFormat::Csv
+        ));
+        assert!(matches!(
+            ImportFormat::from_path("notes"),
+            ImportFormat::Lines
+        ));
+        assert!(matches!(
+            ImportFormat::from_path("notes.org"),
+            ImportFormat::Lines
+        ));
+    }
+
+    #[test]
+    fn parses_one_story_per_line() {
+        let stories = stories("Fix login\n\n  Update footer  \n", ImportFormat::Lines);
+
+        assert_eq!(stories.len(), 2);
+        assert_eq!(stories[0].subject, "Fix login");
+        assert_eq!(stories[1].subject, "Update footer");
+        assert_eq!(stories[1].line, 3);
+    }
+
+    #[test]
+    fn parses_markdown_checklists() {
+        let input = "# Backlog\n\n- [ ] Fix login\n* [x] Update footer\n- [X] Ship\n- plain item\n";
+        let stories = stories(input, ImportFormat::Markdown);
+
+        assert_eq!(stories.len(), 3);
+        assert_eq!(stories[0].subject, "Fix login");
+        assert!(!stories[0].done);
+        assert_eq!(stories[1].subject, "Update footer");
+        assert!(stories[1].done);
+        assert!(stories[2].done);
+    }
+
+    #[test]
+    fn reports_empty_markdown_items() {
+        let stories = parse_stories("- [ ] Fix login\n- [ ]   \n", ImportFormat::Markdown);
+
+        assert!(stories[0].is_ok());
+        assert_eq!(stories[1].as_ref().unwrap_err().0, 2);
+    }
+
+    #[test]
+    fn parses_csv_columns_in_any_order() {
+        let input = "Due,Subject,Assignee,Tags,Status\n\
+                     2026-10-20,\"Fix \"\"login\"\", again\",alice;bob,\"ui, auth\",new\n";
+        let stories = stories(input, ImportFormat::Csv);
+
+        assert_eq!(stories.len(), 1);
+        assert_eq!(stories[0].subject, "Fix \"login\", again");
+        assert_eq!(stories[0].due.as_deref(), Some("2026-10-20"));
+        assert_eq!(stories[0].assign, vec!["alice", "bob"]);
+        assert_eq!(stories[0].tags, vec!["ui", "auth"]);
+        assert_eq!(stories[0].status.as_deref(), Some("new"));
+        assert_eq!(stories[0].line, 2);
+    }
+
+    #[test]
+    fn reports_csv_errors() {
+        let missing_column = parse_stories("name,status\nFix login,new\n", ImportFormat::Csv);
+        assert_eq!(missing_column.len(), 1);
+        assert!(missing_column[0].is_err());
+
+        let missing_subject = parse_stories("subject,status\n,new\nFix,new\n", ImportFormat::Csv);
+        assert_eq!(missing_subject[0].as_ref().unwrap_err().0, 2);
+        assert!(missing_subject[1].is_ok());
+
+        assert!(parse_stories("", ImportFormat::Csv).is_empty());
+    }
+
+    #[test]
+    fn splits_csv_lines() {
+        assert_eq!(split_csv_line("a,,c"), vec!["a", "", "c"]);
+        assert_eq!(split_csv_line("\"a,b\",c"), vec!["a,b", "c"]);
+        assert_eq!(split_csv_line("\"say \"\"hi\"\"\""), vec!["say \"hi\""]);
+    }
+}
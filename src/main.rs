@@ -1,57 +1,237 @@
+mod board;
 mod cli;
+mod git;
+mod hooks;
+mod ics;
+mod import;
 mod taiga;
+mod taskwarrior;
 mod utils;
+mod webhook;
 
-use anyhow::Result;
-use chrono::{DateTime, NaiveDate, Utc};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use colored::Colorize;
 use prettytable::format::consts::FORMAT_CLEAN;
 use prettytable::{row, Cell, Row, Table};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
 use std::process::exit;
-use taiga::{Taiga, TaigaProject, TaigaTask, TaigaTasks};
+use std::thread;
+use std::time::Duration as StdDuration;
+use taiga::{
+    find_attribute, find_member, format_minutes, parse_due_date, ProjectArchive, Taiga,
+    TaigaActivity, TaigaAttribute, TaigaConfig, TaigaEventKind, TaigaProject, TaigaProjectActivity,
+    TaigaRecurrence, TaigaRecurrences, TaigaStatus, TaigaSwimlane, TaigaSyncState, TaigaSyncStory,
+    TaigaTask, TaigaTasks, TaigaTimeEntry, TaigaTimeLog, TaigaTimer, TaigaUser, TaigaUuids,
+    WipPolicy,
+};
 
-use cli::{parse_args, TaigaCmd};
+use board::{parse_board, render_board};
+use cli::{commit_msg_args, parse_args, TaigaCmd};
 use cli::{
-    AssignTaskArgs, BlockTaskArgs, ClientTaskArgs, DeleteTaskArgs, DoneTaskArgs, DueTaskArgs,
-    ModifyTaskArgs, MoveTaskArgs, NewTaskArgs, ProjectUserArgs, RenameTaskArgs, SearchTaskArgs,
-    TeamTaskArgs,
+    AssignTaskArgs, AttachTaskArgs, BlockTaskArgs, BranchTaskArgs, ClientTaskArgs, CommentTaskArgs,
+    CommitMsgArgs, DeleteTaskArgs, DoneTaskArgs, DueTaskArgs, ExportFormat, GetAttachmentArgs,
+    IcsArgs, ImportStoriesArgs, InstallHookArgs, ListAttachmentsArgs, LogTimeArgs, MemberAction,
+    ModifyTaskArgs, MoveTaskArgs, NewTaskArgs, ProjectBoardArgs, ProjectExportArgs,
+    ProjectImportArgs, ProjectMemberArgs, ProjectNewArgs, ProjectRecurArgs, ProjectRestoreArgs,
+    ProjectStatusArgs, ProjectSyncArgs, ProjectUserArgs, RenameTaskArgs, ReopenTaskArgs,
+    SearchTaskArgs, SetAttributeArgs, ShowTaskArgs, StatusAction, StopTaskArgs, TaskFilter,
+    TaskSelector, TeamTaskArgs, TimesheetArgs, VoteTaskArgs, WatchTaskArgs, WebhookServeArgs,
+};
+use git::{
+    branch_name, branch_reference, checkout_branch, commit_references, current_branch, hooks_dir,
+    in_repository,
 };
+use hooks::{find_hooks, has_hooks, run_hook, run_post_hooks, run_pre_hooks};
+use ics::{render_calendar, CalendarEntry};
+use import::{match_created, parse_stories, ImportStory};
+use taskwarrior::{parse_tasks, story_uuid, TaskwarriorTask};
+use utils::slug;
+use webhook::{read_request, respond, verify_signature};
+
+const COMMIT_HOOK_MARKER: &str = "installed by taiga-cli";
 
 fn main() -> Result<()> {
+    // the commit-msg hook must not prompt nor fail, so it runs before the login and the parsing
+    if let Some(args) = commit_msg_args() {
+        taiga_commit_msg(args);
+        return Ok(());
+    }
+
     let mut taiga = match Taiga::from_cache() {
         Some(taiga) => taiga,
         _ => Taiga::auth(None)?,
     };
-    let cmd = parse_args(&Some(taiga.clone()));
+    let config = TaigaConfig::load()?;
+    let cmd = parse_args(&Some(taiga.clone()), &config);
 
     match cmd {
         TaigaCmd::Default => taiga_default(&mut taiga),
         TaigaCmd::Login(args) => {
-            Taiga::auth(args.address);
+            Taiga::auth(args.address)?;
         }
         TaigaCmd::Projects => taiga_projects(&mut taiga),
         TaigaCmd::NewTask(args) => taiga_new(&mut taiga, args),
-        TaigaCmd::MoveTask(args) => taiga_move(&mut taiga, args),
-        TaigaCmd::DoneTask(args) => taiga_done(&mut taiga, args),
-        TaigaCmd::RenameTask(args) => taiga_rename(&mut taiga, args),
-        TaigaCmd::AssignTask(args) => taiga_assign(&mut taiga, args),
-        TaigaCmd::DueTask(args) => taiga_due(&mut taiga, args),
-        TaigaCmd::TeamTask(args) => taiga_team(&mut taiga, args),
-        TaigaCmd::ClientTask(args) => taiga_client(&mut taiga, args),
-        TaigaCmd::BlockTask(args) => taiga_block(&mut taiga, args),
-        TaigaCmd::ModifyTask(args) => taiga_modify(&mut taiga, args),
-        TaigaCmd::DeleteTask(args) => taiga_delete(&mut taiga, args),
+        TaigaCmd::MoveTask(args) => taiga_move(&mut taiga, &config, args),
+        TaigaCmd::DoneTask(args) => taiga_done(&mut taiga, &config, args),
+        TaigaCmd::ReopenTask(args) => taiga_reopen(&mut taiga, &config, args),
+        TaigaCmd::RenameTask(args) => taiga_rename(&mut taiga, &config, args),
+        TaigaCmd::AssignTask(args) => taiga_assign(&mut taiga, &config, args),
+        TaigaCmd::DueTask(args) => taiga_due(&mut taiga, &config, args),
+        TaigaCmd::TeamTask(args) => taiga_team(&mut taiga, &config, args),
+        TaigaCmd::ClientTask(args) => taiga_client(&mut taiga, &config, args),
+        TaigaCmd::BlockTask(args) => taiga_block(&mut taiga, &config, args),
+        TaigaCmd::ModifyTask(args) => taiga_modify(&mut taiga, &config, args),
+        TaigaCmd::DeleteTask(args) => taiga_delete(&mut taiga, &config, args),
+        TaigaCmd::AttachTask(args) => taiga_attach(&mut taiga, &config, args),
+        TaigaCmd::ListAttachments(args) => taiga_attachments(&mut taiga, args),
+        TaigaCmd::GetAttachment(args) => taiga_attachment_get(&mut taiga, args),
+        TaigaCmd::WatchTask(args) => taiga_watch(&mut taiga, &config, args),
+        TaigaCmd::VoteTask(args) => taiga_vote(&mut taiga, &config, args),
+        TaigaCmd::ShowTask(args) => taiga_show(&mut taiga, args),
+        TaigaCmd::CommentTask(args) => taiga_comment(&mut taiga, &config, args),
+        TaigaCmd::SetAttribute(args) => taiga_set(&mut taiga, &config, args),
+        TaigaCmd::BranchTask(args) => taiga_branch(&mut taiga, &config, args),
+        TaigaCmd::StopTask(args) => taiga_stop(&mut taiga, args),
+        TaigaCmd::LogTime(args) => taiga_log(&mut taiga, args),
+        TaigaCmd::Timesheet(args) => taiga_timesheet(&mut taiga, args),
+        TaigaCmd::InstallHook(args) => taiga_install_hook(args),
+        TaigaCmd::CommitMsg(args) => taiga_commit_msg(args),
         TaigaCmd::SearchTask(args) => taiga_search(&mut taiga, args),
         TaigaCmd::ProjectUsers(args) => taiga_users(&mut taiga, args),
+        TaigaCmd::ProjectBoard(args) => taiga_board(&mut taiga, args),
+        TaigaCmd::ProjectStatus(args) => taiga_status(&mut taiga, args),
+        TaigaCmd::ProjectMember(args) => taiga_member(&mut taiga, args),
+        TaigaCmd::ProjectNew(args) => taiga_project_new(&mut taiga, args),
+        TaigaCmd::ProjectRecur(args) => taiga_recur(&mut taiga, args),
+        TaigaCmd::ImportStories(args) => taiga_import_stories(&mut taiga, args),
+        TaigaCmd::ProjectExport(args) => taiga_export(&mut taiga, args),
+        TaigaCmd::ProjectImport(args) => taiga_import(&mut taiga, args),
+        TaigaCmd::ProjectSync(args) => taiga_sync(&mut taiga, args),
+        TaigaCmd::ProjectRestore(args) => taiga_restore(&mut taiga, args),
+        TaigaCmd::Ics(args) => taiga_ics(&mut taiga, args),
+        TaigaCmd::WebhookServe(args) => taiga_webhook_serve(&mut taiga, args),
         other => println!("TODO: {:?}", other),
     }
     Ok(())
 }
 
+// Show what changed since the last time, then the project list
 pub fn taiga_default(taiga: &mut Taiga) {
+    let mut activity = TaigaActivity::from_cache().unwrap_or_default();
+    let now = Utc::now();
+    let today = Local::now().date_naive();
+    let mut earliest = now;
+
+    let mut assigned = Vec::new();
+    let mut overdue = Vec::new();
+    let mut moved = Vec::new();
+    let mut commented = Vec::new();
+
+    for project in taiga.projects.clone() {
+        let previous = activity.projects.get(&project.id).cloned();
+        let since = previous
+            .as_ref()
+            .map(|previous| previous.last_seen)
+            .unwrap_or(now - Duration::days(1));
+        earliest = earliest.min(since);
+
+        let assigned_ids = match taiga.get_open_tasks(project.id) {
+            Ok(tasks) => {
+                let mut assigned_ids = Vec::new();
+                for task in tasks
+                    .iter()
+                    .filter(|task| task.assigned.contains(&taiga.id))
+                {
+                    assigned_ids.push(task.id);
+                    let label = format!("{} #{} {}", project.name, task.reference, task.name);
+
+                    // on the first run every task would be new, so none is reported
+                    if previous
+                        .as_ref()
+                        .is_some_and(|previous| !previous.assigned.contains(&task.id))
+                    {
+                        assigned.push(label.clone());
+                    }
+                    if let Some(due) = task.due.filter(|due| due.date_naive() < today) {
+                        overdue.push(format!("{} {}", label, format_due(&due)));
+                    }
+                }
+                Some(assigned_ids)
+            }
+            Err(err) => {
+                eprintln!("Error, could not get tasks of {}: {}", project.name, err);
+                None
+            }
+        };
+
+        let seen = match taiga.get_events(project.id, since) {
+            Ok(events) => {
+                for event in events
+                    .iter()
+                    .filter(|event| event.user_id != Some(taiga.id))
+                {
+                    let label = format!("{} #{} {}", project.name, event.reference, event.name);
+                    match &event.kind {
+                        TaigaEventKind::Moved(from, to) => moved.push(format!(
+                            "{}, {} -> {} by {}",
+                            label, from, to, event.username
+                        )),
+                        TaigaEventKind::Commented => {
+                            commented.push(format!("{} by {}", label, event.username))
+                        }
+                    }
+                }
+                now
+            }
+            Err(err) => {
+                eprintln!("Error, could not get activity of {}: {}", project.name, err);
+                since
+            }
+        };
+
+        // what could not be fetched is kept as it was, to be reported on the next run
+        let assigned_ids = assigned_ids.or(previous.map(|previous| previous.assigned));
+        if let Some(assigned_ids) = assigned_ids {
+            activity.projects.insert(
+                project.id,
+                TaigaProjectActivity {
+                    last_seen: seen,
+                    assigned: assigned_ids,
+                },
+            );
+        }
+    }
+
+    let sections = [
+        ("Assigned to you", assigned),
+        ("Moved", moved),
+        ("Commented", commented),
+        ("Overdue", overdue),
+    ];
+    if sections.iter().all(|(_, lines)| lines.is_empty()) {
+        println!(
+            "Nothing new since {}",
+            earliest.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        );
+    }
+    for (title, lines) in sections.iter().filter(|(_, lines)| !lines.is_empty()) {
+        println!("{}", format!("{}:", title).bold());
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+
+    println!("{}", "Projects:".bold());
     for project in &taiga.projects {
-        println!("{}", project.name);
+        println!("  {}", project.name);
     }
+
+    activity.save_cache();
 }
 
 pub fn taiga_projects(taiga: &mut Taiga) {
@@ -65,7 +245,10 @@ pub fn taiga_projects(taiga: &mut Taiga) {
     }
 
     taiga.projects = projects;
-    taiga.save_cache();
+    taiga.save_cache().unwrap_or_else(|err| {
+        eprintln!("Error, could not save cache: {}", err);
+        exit(1);
+    });
 }
 
 pub fn taiga_search(taiga: &mut Taiga, args: SearchTaskArgs) {
@@ -73,14 +256,105 @@ pub fn taiga_search(taiga: &mut Taiga, args: SearchTaskArgs) {
         eprintln!("{}", err);
         exit(1);
     });
-    let id = project.id;
 
-    let mut tasks = taiga.get_tasks(id).unwrap_or_else(|err| {
-        eprintln!("Error, could not get tasks: {}", err);
-        exit(1);
-    });
+    let interval = match args.watch {
+        Some(interval) => interval,
+        None => {
+            let (tasks, project) =
+                search_tasks(taiga, project.id, &args.filter, !args.columns.is_empty())
+                    .unwrap_or_else(|err| {
+                        eprintln!("Error, {}", err);
+                        exit(1);
+                    });
+            let columns = find_columns(&tasks, &args.columns);
+            search_table(&tasks, &project, &HashSet::new(), &columns).printstd();
+            return;
+        }
+    };
+
+    // redrawing the results in place, highlighting what changed since the previous poll
+    let mut previous: Option<HashMap<i32, TaigaTask>> = None;
+    loop {
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "Every {}s, last refresh at {}\n",
+            interval,
+            Local::now().format("%H:%M:%S")
+        );
+
+        match search_tasks(taiga, project.id, &args.filter, !args.columns.is_empty()) {
+            Ok((tasks, project)) => {
+                let changed = match &previous {
+                    Some(previous) => changed_tasks(previous, &tasks.tasks),
+                    None => HashSet::new(),
+                };
+
+                let columns = find_columns(&tasks, &args.columns);
+                search_table(&tasks, &project, &changed, &columns).printstd();
+                previous = Some(
+                    tasks
+                        .tasks
+                        .into_iter()
+                        .map(|task| (task.id, task))
+                        .collect(),
+                );
+            }
+            Err(err) => eprintln!("Error, {}", err),
+        }
+
+        io::stdout().flush().expect("Could not flush stdout");
+        thread::sleep(StdDuration::from_secs(interval));
+    }
+}
+
+// The tasks that are new or whose status, assignees or due date changed since the last poll
+fn changed_tasks(previous: &HashMap<i32, TaigaTask>, tasks: &[TaigaTask]) -> HashSet<i32> {
+    tasks
+        .iter()
+        .filter(|task| match previous.get(&task.id) {
+            Some(old) => {
+                old.version != task.version
+                    && (old.status_id != task.status_id
+                        || old.assigned != task.assigned
+                        || old.due != task.due)
+            }
+            None => true,
+        })
+        .map(|task| task.id)
+        .collect()
+}
+
+// Fetch the tasks of the project, refresh the cache and keep the ones matching the filter,
+// loading their custom attribute values when asked to
+fn search_tasks(
+    taiga: &mut Taiga,
+    id: i32,
+    filter: &TaskFilter,
+    attributes: bool,
+) -> Result<(TaigaTasks, TaigaProject)> {
+    let mut tasks =
+        fetch_tasks(taiga, id, filter).map_err(|err| anyhow!("could not get tasks: {}", err))?;
+
+    let project = match TaigaProject::from_cache(id) {
+        Ok(Some(project))
+            if tasks
+                .iter()
+                .flat_map(|task| &task.assigned)
+                .all(|id| project.members.iter().any(|m| m.id == *id)) =>
+        {
+            project
+        }
+        _ => {
+            let project = taiga
+                .get_project(id)
+                .map_err(|err| anyhow!("could not get project: {}", err))?;
+            project
+                .save_cache()
+                .map_err(|err| anyhow!("could not save cache: {}", err))?;
+            project
+        }
+    };
 
-    tasks.retain(|task| !task.closed);
     tasks.sort_by(|a, b| {
         b.status_id
             .cmp(&a.status_id) // Reverse the order to ensure higher status_ids go first
@@ -97,226 +371,58 @@ pub fn taiga_search(taiga: &mut Taiga, args: SearchTaskArgs) {
             })
     });
 
-    let project = match TaigaProject::from_cache(id) {
-        Ok(Some(project)) => {
-            let members: Vec<i32> = tasks
-                .iter()
-                .flat_map(|task| &task.assigned)
-                .cloned()
-                .collect::<HashSet<i32>>()
-                .into_iter()
-                .collect();
-            let all_present = members
-                .iter()
-                .all(|id| project.members.iter().any(|m| m.id == *id));
-            if all_present {
-                project
-            } else {
-                let project = taiga.get_project(project.id).unwrap_or_else(|err| {
-                    eprintln!("Error, could not get project: {}", err);
-                    exit(1);
-                });
-                project.save_cache();
-                project
-            }
-        }
-        _ => {
-            let project = taiga.get_project(project.id).unwrap_or_else(|err| {
-                eprintln!("Error, could not get project: {}", err);
-                exit(1);
-            });
-            project.save_cache();
-            project
-        }
-    };
+    if let Some(cached) = TaigaTasks::from_cache(project.id) {
+        keep_attributes(&mut tasks, &cached.tasks);
+    }
 
     let taiga_tasks = TaigaTasks {
         id: project.id,
         tasks: tasks.clone(),
         members: project.members.clone(),
         statuses: project.statuses.clone(),
+        attributes: project.attributes.clone(),
     };
     taiga_tasks.save_cache();
 
-    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
-        for status in &args.include_statuses {
-            if !tasks.statuses.iter().any(|s| s.slug == *status) {
-                return true;
-            }
-        }
-        for status in &args.exclude_statuses {
-            if !tasks.statuses.iter().any(|s| s.slug == *status) {
-                return true;
-            }
-        }
-        for username in &args.include_assigned {
-            if username != "me" && !tasks.members.iter().any(|m| m.username == *username) {
-                return true;
-            }
-        }
-        for username in &args.exclude_assigned {
-            if username != "me" && !tasks.members.iter().any(|m| m.username == *username) {
-                return true;
-            }
-        }
-        false
-    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| filter_outdated(tasks, filter));
 
-    let mut include_status_ids = Vec::new();
-    for status in args.include_statuses {
-        let status_id = if let Some(status) = tasks.statuses.iter().find(|s| s.slug == *status) {
-            status.id
-        } else {
-            eprintln!("Error, could not find given status");
-            exit(1);
-        };
-        include_status_ids.push(status_id);
+    tasks.tasks = filter_with_attributes(taiga, &tasks, filter);
+    if attributes {
+        load_attributes(taiga, &mut tasks.tasks)?;
     }
+    tasks.clone().save_cache();
 
-    let mut exclude_status_ids = Vec::new();
-    for status in args.exclude_statuses {
-        let status_id = if let Some(status) = tasks.statuses.iter().find(|s| s.slug == *status) {
-            status.id
-        } else {
-            eprintln!("Error, could not find given status");
-            exit(1);
-        };
-        exclude_status_ids.push(status_id);
-    }
+    Ok((tasks, project))
+}
 
-    let mut include_member_ids = Vec::new();
-    for username in args.include_assigned {
-        let member_id = if username == "me" {
-            tasks
-                .members
-                .iter()
-                .find(|member| member.id == taiga.id)
-                .map(|m| m.id)
-                .unwrap_or_else(|| {
-                    eprintln!("Could not find your username on the project");
-                    exit(1);
-                })
-        } else {
-            tasks
-                .members
-                .iter()
-                .find(|member| member.username == username)
-                .map(|m| m.id)
-                .unwrap_or_else(|| {
-                    eprintln!("Could not find username on the project");
-                    exit(1);
-                })
-        };
-        include_member_ids.push(member_id);
-    }
-    let mut exclude_member_ids = Vec::new();
-    for username in args.exclude_assigned {
-        let member_id = if username == "me" {
-            tasks
-                .members
-                .iter()
-                .find(|member| member.id == taiga.id)
-                .map(|m| m.id)
-                .unwrap_or_else(|| {
-                    eprintln!("Could not find your username on the project");
-                    exit(1);
-                })
-        } else {
-            tasks
-                .members
-                .iter()
-                .find(|member| member.username == username)
-                .map(|m| m.id)
+// The custom attributes shown as extra search columns
+fn find_columns(tasks: &TaigaTasks, names: &[String]) -> Vec<TaigaAttribute> {
+    names
+        .iter()
+        .map(|name| {
+            find_attribute(&tasks.attributes, name)
+                .cloned()
                 .unwrap_or_else(|| {
-                    eprintln!("Could not find username on the project");
+                    eprintln!("Error, could not find attribute {}", name);
                     exit(1);
                 })
-        };
-        exclude_member_ids.push(member_id);
-    }
+        })
+        .collect()
+}
 
+fn search_table(
+    tasks: &TaigaTasks,
+    project: &TaigaProject,
+    highlighted: &HashSet<i32>,
+    columns: &[TaigaAttribute],
+) -> Table {
     let mut table = Table::new();
     table.set_format(*FORMAT_CLEAN);
-    table.add_row(row!["ID", "STATUS", "DUE", "NAME", "ASSIGN", "T", "C", "B"]);
-
-    let filter_tasks: Vec<TaigaTask> = tasks
-        .tasks
-        .into_iter()
-        .filter(|task| {
-            if let Some(team) = args.team {
-                if task.team != team {
-                    return false;
-                }
-            }
-
-            if let Some(client) = args.client {
-                if task.client != client {
-                    return false;
-                }
-            }
-
-            if let Some(block) = args.block {
-                if task.blocked != block {
-                    return false;
-                }
-            }
-
-            if let Some(due_date) = &args.due_date {
-                if due_date.is_empty() {
-                    if task.due.is_some() {
-                        return false;
-                    }
-                } else if let Some(task_due) = task.due {
-                    let a = task_due.date_naive();
-                    let b = NaiveDate::parse_from_str(due_date, "%Y-%m-%d")
-                        .expect("Could not parse due date");
-                    if a > b {
-                        return false;
-                    }
-                } else {
-                    return false;
-                }
-            }
-
-            if !include_member_ids.is_empty()
-                && !task
-                    .assigned
-                    .iter()
-                    .any(|id| include_member_ids.iter().any(|member_id| member_id == id))
-            {
-                return false;
-            }
-
-            if !exclude_member_ids.is_empty()
-                && task
-                    .assigned
-                    .iter()
-                    .any(|id| exclude_member_ids.iter().any(|member_id| member_id == id))
-            {
-                return false;
-            }
-
-            if !include_status_ids.is_empty()
-                && !include_status_ids.iter().any(|id| *id == task.status_id)
-            {
-                return false;
-            }
-
-            if !exclude_status_ids.is_empty()
-                && exclude_status_ids.iter().any(|id| *id == task.status_id)
-            {
-                return false;
-            }
-
-            if !fzf_match(&task.name, &args.query) {
-                return false;
-            }
-
-            true
-        })
-        .collect();
-    tasks.tasks = filter_tasks;
-    tasks.clone().save_cache();
+    let mut header = row!["ID", "STATUS", "DUE", "NAME", "ASSIGN", "T", "C", "B"];
+    for attribute in columns {
+        header.add_cell(Cell::new(&attribute.name.to_uppercase()));
+    }
+    table.add_row(header);
 
     for (i, task) in tasks.tasks.iter().enumerate() {
         let assigned = task
@@ -340,7 +446,7 @@ pub fn taiga_search(taiga: &mut Taiga, args: SearchTaskArgs) {
             "".to_string()
         };
 
-        table.add_row(Row::new(vec![
+        let mut cells = vec![
             Cell::new(&format!("{}", i + 1)),
             Cell::new(&task.status),
             Cell::new(&due),
@@ -349,9 +455,27 @@ pub fn taiga_search(taiga: &mut Taiga, args: SearchTaskArgs) {
             Cell::new(if task.team { "Y" } else { "" }),
             Cell::new(if task.client { "Y" } else { "" }),
             Cell::new(if task.blocked { "Y" } else { "" }),
-        ]));
+        ];
+        for attribute in columns {
+            cells.push(Cell::new(
+                task.attributes
+                    .get(&attribute.id)
+                    .map(String::as_str)
+                    .unwrap_or(""),
+            ));
+        }
+        let cells = if highlighted.contains(&task.id) {
+            cells
+                .into_iter()
+                .map(|cell| cell.style_spec("bFy"))
+                .collect()
+        } else {
+            cells
+        };
+        table.add_row(Row::new(cells));
     }
-    table.printstd();
+
+    table
 }
 
 pub fn taiga_new(taiga: &mut Taiga, args: NewTaskArgs) {
@@ -367,7 +491,7 @@ pub fn taiga_new(taiga: &mut Taiga, args: NewTaskArgs) {
             }
         }
         for username in &args.assign {
-            if username != "me" && !tasks.members.iter().any(|m| m.username == *username) {
+            if member_outdated(&tasks.members, username) {
                 return true;
             }
         }
@@ -392,68 +516,137 @@ pub fn taiga_new(taiga: &mut Taiga, args: NewTaskArgs) {
 
     let mut assigned_ids = Vec::new();
     for username in args.assign {
-        let member_id = if username == "me" {
-            tasks
-                .members
-                .iter()
-                .find(|member| member.id == taiga.id)
-                .map(|m| m.id)
-                .unwrap_or_else(|| {
-                    eprintln!("Could not find your username on the project");
-                    exit(1);
-                })
-        } else {
-            tasks
-                .members
-                .iter()
-                .find(|member| member.username == username)
-                .map(|m| m.id)
-                .unwrap_or_else(|| {
-                    eprintln!("Could not find username on the project");
-                    exit(1);
-                })
-        };
+        let member_id = resolve_member(taiga.id, &tasks.members, &username);
         assigned_ids.push(member_id);
     }
 
-    if let Ok(new_task) = taiga
-        .new_task(
-            project.id,
-            status_id,
-            args.name.clone(),
-            assigned_ids.clone(),
-            args.team,
-            args.client,
-            args.block,
-        )
-        .as_mut()
-    {
-        let task_id = new_task.id;
-        let version = new_task.version;
+    let points = match args.points {
+        Some(points) => taiga
+            .get_role_points(project.id, points)
+            .unwrap_or_else(|err| {
+                eprintln!("Error, could not find points: {}", err);
+                exit(1);
+            }),
+        None => HashMap::new(),
+    };
 
-        if let Ok(mod_task) = taiga.modify_task(
-            task_id,
+    let draft = TaigaTask {
+        id: 0,
+        reference: 0,
+        name: args.name.clone(),
+        status_id,
+        status: tasks
+            .statuses
+            .iter()
+            .find(|s| s.id == status_id)
+            .map(|s| s.slug.clone())
+            .unwrap_or_default(),
+        team: args.team,
+        client: args.client,
+        blocked: args.block,
+        assigned: assigned_ids,
+        due: parse_due_date(&args.due_date),
+        swimlane: None,
+        closed: tasks
+            .statuses
+            .iter()
+            .any(|s| s.id == status_id && s.is_closed),
+        tags: args.tags.clone(),
+        watchers: Vec::new(),
+        votes: 0,
+        version: 0,
+        attributes: HashMap::new(),
+    };
+    let draft = match run_pre_hooks("new", None, Some(draft.clone())) {
+        Ok(rewritten) => rewritten.unwrap_or(draft),
+        Err(err) => {
+            eprintln!("Error, could not create new task: {}", err);
+            exit(1);
+        }
+    };
+    let status_id = draft.status_id;
+
+    let new_task = taiga
+        .new_task(
+            project.id,
             status_id,
-            args.name,
-            assigned_ids,
-            args.due_date,
-            args.team,
-            args.client,
-            args.block,
-            version,
-        ) {
-            *new_task = mod_task;
-            let new_task = new_task.clone();
-            tasks.tasks.push(new_task);
-            tasks.save_cache();
-        } else {
-            eprintln!("Error, could not modify new task");
+            draft.name.clone(),
+            draft.assigned.clone(),
+            draft.team,
+            draft.client,
+            draft.blocked,
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("Error, could not create new task: {}", err);
             exit(1);
+        });
+
+    let new_task = match taiga.modify_task(
+        new_task.id,
+        status_id,
+        draft.name,
+        draft.assigned,
+        draft.due.map(|due| due.format("%Y-%m-%d").to_string()),
+        draft.team,
+        draft.client,
+        draft.blocked,
+        new_task.version,
+    ) {
+        Ok(mod_task) => mod_task,
+        Err(err) => new_task_failed(&mut tasks, new_task, "set its assignees and due date", err),
+    };
+
+    let new_task = if args.description.is_some() || !draft.tags.is_empty() || !points.is_empty() {
+        match taiga.detail_task(
+            new_task.id,
+            args.description,
+            Some(draft.tags).filter(|tags| !tags.is_empty()),
+            points,
+            new_task.version,
+        ) {
+            Ok(detail_task) => detail_task,
+            Err(err) => new_task_failed(
+                &mut tasks,
+                new_task,
+                "set its description, tags and points",
+                err,
+            ),
         }
     } else {
-        eprintln!("Error, could not create new task");
-        exit(1);
+        new_task
+    };
+
+    if let (Some(interval), Some(due)) = (args.recur, new_task.due) {
+        let mut recurrences = load_recurrences(project.id);
+        recurrences.recurrences.push(TaigaRecurrence {
+            task_id: new_task.id,
+            name: new_task.name.clone(),
+            status_id,
+            assigned: new_task.assigned.clone(),
+            team: new_task.team,
+            client: new_task.client,
+            blocked: new_task.blocked,
+            interval,
+            due: due.date_naive(),
+        });
+        save_recurrences(&recurrences);
     }
+
+    run_post_hooks("new", None, Some(&new_task));
+    tasks.tasks.push(new_task);
+    tasks.save_cache();
+}
+
+// A story that could be created but not completed is kept and named, so that it can be fixed
+// by hand instead of being created again
+fn new_task_failed(tasks: &mut TaigaTasks, task: TaigaTask, what: &str, err: anyhow::Error) -> ! {
+    eprintln!(
+        "Error, created #{} {} but could not {}: {}",
+        task.reference, task.name, what, err
+    );
+    tasks.tasks.push(task);
+    tasks.clone().save_cache();
+    exit(1);
 }
 
 pub fn taiga_users(taiga: &mut Taiga, args: ProjectUserArgs) {
@@ -464,379 +657,2939 @@ pub fn taiga_users(taiga: &mut Taiga, args: ProjectUserArgs) {
     let tasks = taiga.tasks_from_cache(project.id, |_| true);
     tasks.clone().save_cache();
 
-    for user in tasks.members {
-        println!("{}", user.username);
+    // the workload counts every open story, not only those of the last search
+    let open_tasks = taiga.get_open_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
+        exit(1);
+    });
+    let today = Local::now().date_naive();
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT_CLEAN);
+    table.add_row(row![
+        "USERNAME", "NAME", "ROLE", "EMAIL", "ACTIVE", "OPEN", "OVERDUE"
+    ]);
+
+    for user in &tasks.members {
+        let (open, overdue) = workload(user.id, &open_tasks, today);
+
+        table.add_row(Row::new(vec![
+            Cell::new(&user.username),
+            Cell::new(&user.full_name),
+            Cell::new(&user.role),
+            Cell::new(user.email.as_deref().unwrap_or("")),
+            Cell::new(if user.is_active { "Y" } else { "" }),
+            Cell::new(&open.to_string()),
+            Cell::new(&overdue.to_string()),
+        ]));
     }
+
+    table.printstd();
 }
 
-pub fn taiga_move(taiga: &mut Taiga, args: MoveTaskArgs) {
-    // getting the necessary information
+// The number of open stories assigned to a user, and how many of them are overdue
+fn workload(user_id: i32, open_tasks: &[TaigaTask], today: NaiveDate) -> (usize, usize) {
+    let assigned: Vec<&TaigaTask> = open_tasks
+        .iter()
+        .filter(|task| task.assigned.contains(&user_id))
+        .collect();
+    let overdue = assigned
+        .iter()
+        .filter(|task| task.due.is_some_and(|due| due.date_naive() < today))
+        .count();
+
+    (assigned.len(), overdue)
+}
+
+// Print the kanban board, a column per status that is not archived and a row per swimlane
+pub fn taiga_board(taiga: &mut Taiga, args: ProjectBoardArgs) {
     let project = taiga.find_project(args.project).unwrap_or_else(|err| {
         eprintln!("{}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
-        !tasks
-            .statuses
-            .iter()
-            .any(|status| status.slug == args.status)
+    let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get project: {}", err);
+        exit(1);
     });
-
-    // checking status is present on the project
-    let status_id = if let Some(status) = tasks
-        .statuses
-        .iter()
-        .find(|status| status.slug == args.status)
-    {
-        status.id
-    } else {
-        eprintln!("Error, could not find given status");
+    project.save_cache().unwrap_or_else(|err| {
+        eprintln!("Error, could not save cache: {}", err);
         exit(1);
-    };
+    });
+    let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
+        exit(1);
+    });
 
-    let task = tasks.get_task(args.id);
+    let statuses: Vec<&TaigaStatus> = project.statuses.iter().filter(|s| !s.is_archived).collect();
 
-    // pushing the changes
-    if let Ok(new_task) = taiga.move_task(task.id, status_id, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not move task");
-        exit(1);
+    let lanes = board_lanes(&project.swimlanes, &tasks);
+    let named_lanes = !project.swimlanes.is_empty();
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT_CLEAN);
+
+    let mut header = Vec::new();
+    if named_lanes {
+        header.push(Cell::new(""));
+    }
+    for status in &statuses {
+        let count = tasks
+            .iter()
+            .filter(|task| task.status_id == status.id)
+            .count();
+        let label = match status.wip_limit.filter(|limit| *limit > 0) {
+            Some(limit) => format!("{} {}/{}", status.slug, count, limit),
+            None => format!("{} {}", status.slug, count),
+        };
+        let label = match parse_color(&status.color) {
+            Some((r, g, b)) => label.truecolor(r, g, b).bold(),
+            None => label.bold(),
+        };
+        header.push(Cell::new(&label.to_string()));
+    }
+    table.add_row(Row::new(header));
+
+    for (name, lane_tasks) in &lanes {
+        let mut cells = Vec::new();
+        if named_lanes {
+            cells.push(Cell::new(&name.bold().to_string()));
+        }
+        for status in &statuses {
+            let items = lane_tasks
+                .iter()
+                .filter(|task| task.status_id == status.id)
+                .map(|task| format!("#{} {}", task.reference, task.name))
+                .collect::<Vec<String>>()
+                .join("\n");
+            cells.push(Cell::new(&items));
+        }
+        table.add_row(Row::new(cells));
+    }
+
+    table.printstd();
+}
+
+// Group the stories of the board by swimlane, stories outside of the known swimlanes go to a
+// last lane, the only one without swimlanes
+fn board_lanes<'a>(
+    swimlanes: &[TaigaSwimlane],
+    tasks: &'a [TaigaTask],
+) -> Vec<(String, Vec<&'a TaigaTask>)> {
+    let mut lanes: Vec<(String, Vec<&TaigaTask>)> = swimlanes
+        .iter()
+        .map(|lane| {
+            (
+                lane.name.clone(),
+                tasks
+                    .iter()
+                    .filter(|task| task.swimlane == Some(lane.id))
+                    .collect(),
+            )
+        })
+        .collect();
+    let unclassified: Vec<&TaigaTask> = tasks
+        .iter()
+        .filter(|task| !swimlanes.iter().any(|lane| task.swimlane == Some(lane.id)))
+        .collect();
+    if lanes.is_empty() || !unclassified.is_empty() {
+        lanes.push(("Unclassified".to_string(), unclassified));
     }
+    lanes
 }
 
-pub fn taiga_delete(taiga: &mut Taiga, args: DeleteTaskArgs) {
+// Parse a status color such as #70728F
+fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+pub fn taiga_status(taiga: &mut Taiga, args: ProjectStatusArgs) {
     let project = taiga.find_project(args.project).unwrap_or_else(|err| {
         eprintln!("{}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| tasks.statuses.is_empty());
-    let task = tasks.get_task(args.id);
+    let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get project: {}", err);
+        exit(1);
+    });
+
+    let find_status = |name: &str| {
+        let name = slug(name.to_string());
+        project
+            .statuses
+            .iter()
+            .find(|status| status.slug == name)
+            .cloned()
+            .unwrap_or_else(|| {
+                eprintln!("Error, could not find status {}", name);
+                exit(1);
+            })
+    };
+
+    let result = match args.action {
+        StatusAction::Add {
+            name,
+            color,
+            closed,
+            wip_limit,
+        } => {
+            let order = project.statuses.iter().map(|s| s.order + 1).max();
+            taiga
+                .new_status(
+                    project.id,
+                    name.clone(),
+                    color.unwrap_or_else(|| "#999999".to_string()),
+                    closed,
+                    wip_limit,
+                    order.unwrap_or(1),
+                )
+                .map(|_| format!("Added status {}", name))
+        }
+        StatusAction::Rename { status, name } => {
+            let status = find_status(&status);
+            taiga
+                .rename_status(status.id, name.clone())
+                .map(|_| format!("Renamed status {} to {}", status.slug, name))
+        }
+        StatusAction::Reorder { statuses } => {
+            let ids: Vec<i32> = statuses.iter().map(|name| find_status(name).id).collect();
+            let ids = status_order(&ids, &project.statuses);
+            taiga
+                .reorder_statuses(project.id, &ids)
+                .map(|_| "Reordered statuses".to_string())
+        }
+        StatusAction::Close { status, closed } => {
+            let status = find_status(&status);
+            taiga.close_status(status.id, closed).map(|_| {
+                if closed {
+                    format!("Status {} now closes its stories", status.slug)
+                } else {
+                    format!("Status {} now keeps its stories open", status.slug)
+                }
+            })
+        }
+        StatusAction::Delete { status, move_to } => {
+            let status = find_status(&status);
+            let move_to = match move_to {
+                Some(move_to) => find_status(&move_to),
+                None => project
+                    .statuses
+                    .iter()
+                    .find(|s| s.id != status.id && !s.is_archived)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        eprintln!("Error, could not find a status to move the stories to");
+                        exit(1);
+                    }),
+            };
+            if move_to.id == status.id {
+                eprintln!("Error, cannot move the stories to the deleted status");
+                exit(1);
+            }
+            taiga.delete_status(status.id, move_to.id).map(|_| {
+                format!(
+                    "Deleted status {}, its stories moved to {}",
+                    status.slug, move_to.slug
+                )
+            })
+        }
+    };
 
-    if taiga.delete_task(task.id).is_err() {
-        eprintln!("Error, could not delete task");
+    let message = result.unwrap_or_else(|err| {
+        eprintln!("Error, could not change statuses: {}", err);
+        exit(1);
+    });
+    taiga.refresh_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not refresh project: {}", err);
         exit(1);
+    });
+    println!("{}", message);
+}
+
+// The new order of the statuses, the given ones first without repeats and then the others in
+// their current order
+fn status_order(given: &[i32], statuses: &[TaigaStatus]) -> Vec<i32> {
+    let mut seen = HashSet::new();
+    let mut ids: Vec<i32> = given.to_vec();
+    ids.retain(|id| seen.insert(*id));
+    for status in statuses {
+        if !ids.contains(&status.id) {
+            ids.push(status.id);
+        }
     }
+    ids
 }
 
-pub fn taiga_done(taiga: &mut Taiga, args: DoneTaskArgs) {
-    // getting the necessary information
+pub fn taiga_member(taiga: &mut Taiga, args: ProjectMemberArgs) {
     let project = taiga.find_project(args.project).unwrap_or_else(|err| {
         eprintln!("{}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| tasks.statuses.is_empty());
 
-    // getting the appropriate status for done
-    let status_id = if let Some(status) = tasks.statuses.iter().find(|status| status.is_closed) {
-        status.id
-    } else if let Some(status) = tasks.statuses.iter().find(|status| status.slug == "done") {
-        status.id
-    } else {
-        tasks
-            .statuses
-            .last()
-            .expect("No statuses in the project")
-            .id
+    let result = match args.action {
+        MemberAction::Invite { email, role } => taiga
+            .get_role(project.id, &role)
+            .and_then(|role_id| taiga.invite_member(project.id, email.clone(), role_id))
+            .map(|_| format!("Invited {} as {}", email, role)),
+        MemberAction::Remove { username } => {
+            let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+                eprintln!("Error, could not get project: {}", err);
+                exit(1);
+            });
+            let user_id = resolve_member(taiga.id, &project.members, &username);
+            let username = project
+                .members
+                .iter()
+                .find(|member| member.id == user_id)
+                .map(|member| member.username.clone())
+                .unwrap_or(username);
+            taiga
+                .remove_member(project.id, user_id)
+                .map(|_| format!("Removed {} from {}", username, project.name))
+        }
     };
 
-    let task = tasks.get_task(args.id);
+    let message = result.unwrap_or_else(|err| {
+        eprintln!("Error, could not change members: {}", err);
+        exit(1);
+    });
+    taiga.refresh_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not refresh project: {}", err);
+        exit(1);
+    });
+    println!("{}", message);
+}
 
-    // pushing the change
-    if let Ok(new_task) = taiga.move_task(task.id, status_id, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not done task");
+pub fn taiga_project_new(taiga: &mut Taiga, args: ProjectNewArgs) {
+    let description = args.description.unwrap_or_else(|| args.name.clone());
+    let project = taiga
+        .new_project(args.name, description, &args.template)
+        .unwrap_or_else(|err| {
+            eprintln!("Error, could not create project: {}", err);
+            exit(1);
+        });
+    project.save_cache().unwrap_or_else(|err| {
+        eprintln!("Error, could not save cache: {}", err);
         exit(1);
-    }
+    });
+
+    // the project list is what project names are checked against
+    taiga.projects = taiga.get_projects().unwrap_or_else(|err| {
+        eprintln!("Error, could not get projects: {}", err);
+        exit(1);
+    });
+    taiga.save_cache().unwrap_or_else(|err| {
+        eprintln!("Error, could not save cache: {}", err);
+        exit(1);
+    });
+
+    println!("Created project {}", project.name);
 }
 
-pub fn taiga_rename(taiga: &mut Taiga, args: RenameTaskArgs) {
-    // getting necessary information
+pub fn taiga_recur(taiga: &mut Taiga, args: ProjectRecurArgs) {
     let project = taiga.find_project(args.project).unwrap_or_else(|err| {
         eprintln!("{}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
-    let task = tasks.get_task(args.id);
-
-    // pushing the change
-    if let Ok(new_task) = taiga.rename_task(task.id, args.name, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not rename task");
+    let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get project: {}", err);
+        exit(1);
+    });
+    let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
         exit(1);
+    });
+
+    let spawned = spawn_recurrences(taiga, &project, &tasks);
+    for task in &spawned {
+        println!("Created {}", task.name);
+    }
+
+    if let Some(mut cached) = TaigaTasks::from_cache(project.id) {
+        cached.tasks.extend(spawned);
+        cached.save_cache();
+    }
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT_CLEAN);
+    table.add_row(row!["RECUR", "DUE", "NAME"]);
+    for recurrence in load_recurrences(project.id).recurrences {
+        table.add_row(Row::new(vec![
+            Cell::new(&recurrence.interval),
+            Cell::new(&recurrence.due.format("%Y-%m-%d").to_string()),
+            Cell::new(&recurrence.name),
+        ]));
     }
+    table.printstd();
 }
 
-pub fn taiga_assign(taiga: &mut Taiga, args: AssignTaskArgs) {
-    // getting necessary information
-    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
-        eprintln!("{}", err);
+// The recurrences are written by the user, so they are never replaced when unreadable
+fn load_recurrences(id: i32) -> TaigaRecurrences {
+    TaigaRecurrences::load(id).unwrap_or_else(|err| {
+        eprintln!("Error, could not load recurrences: {:#}", err);
+        exit(1);
+    })
+}
+
+fn save_recurrences(recurrences: &TaigaRecurrences) {
+    recurrences.save().unwrap_or_else(|err| {
+        eprintln!("Error, could not save recurrences: {:#}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
-        args.username != "me"
-            && !tasks
-                .members
-                .iter()
-                .any(|member| member.username == args.username)
+}
+
+fn load_timelog() -> TaigaTimeLog {
+    TaigaTimeLog::load().unwrap_or_else(|err| {
+        eprintln!("Error, could not load the time log: {:#}", err);
+        exit(1);
+    })
+}
+
+fn save_timelog(log: &TaigaTimeLog) {
+    log.save().unwrap_or_else(|err| {
+        eprintln!("Error, could not save the time log: {:#}", err);
+        exit(1);
     });
+}
 
-    let member_id = if args.username == "me" {
-        tasks
-            .members
-            .iter()
-            .find(|member| member.id == taiga.id)
-            .map(|m| m.id)
-            .unwrap_or_else(|| {
-                eprintln!("Could not find your username on the project");
-                exit(1);
-            })
-    } else {
-        tasks
-            .members
+// Create the next occurrence of every recurring task whose current occurrence is done
+fn spawn_recurrences(
+    taiga: &mut Taiga,
+    project: &TaigaProject,
+    tasks: &[TaigaTask],
+) -> Vec<TaigaTask> {
+    let mut recurrences = load_recurrences(project.id);
+    let today = Local::now().date_naive();
+    let mut spawned = Vec::new();
+
+    let mut index = 0;
+    while index < recurrences.recurrences.len() {
+        let recurrence = recurrences.recurrences[index].clone();
+        let current = match tasks.iter().find(|task| task.id == recurrence.task_id) {
+            Some(task) => task.clone(),
+            None => match taiga.find_task(recurrence.task_id) {
+                Ok(Some(task)) => task,
+                // the current occurrence was deleted, which ends the recurrence
+                Ok(None) => {
+                    recurrences.recurrences.remove(index);
+                    save_recurrences(&recurrences);
+                    continue;
+                }
+                Err(err) => {
+                    eprintln!("Warning, could not get {}: {}", recurrence.name, err);
+                    index += 1;
+                    continue;
+                }
+            },
+        };
+
+        let done = project
+            .statuses
             .iter()
-            .find(|member| member.username == args.username)
-            .map(|m| m.id)
-            .unwrap_or_else(|| {
-                eprintln!("Could not find username on the project");
-                exit(1);
-            })
-    };
+            .any(|status| status.id == current.status_id && status.is_closed);
+        let due = match recurrence.next_due(today) {
+            Some(due) if done => due,
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
 
-    let task = tasks.get_task(args.id);
+        let task = match taiga.new_task(
+            project.id,
+            recurrence.status_id,
+            recurrence.name.clone(),
+            recurrence.assigned.clone(),
+            recurrence.team,
+            recurrence.client,
+            recurrence.blocked,
+        ) {
+            Ok(task) => task,
+            Err(err) => {
+                eprintln!(
+                    "Error, could not create next occurrence of {}: {}",
+                    recurrence.name, err
+                );
+                index += 1;
+                continue;
+            }
+        };
 
-    // making sure the user can be (de)added from the task
-    let mut assigned = task.assigned.clone();
-    if args.remove {
-        if assigned.iter().any(|m| *m == member_id) {
-            assigned = assigned
-                .iter()
-                .filter(|&&m| m != member_id)
-                .copied()
-                .collect();
-        } else {
-            eprintln!("Error, the user is not assigned to the task, cannot remove");
-            exit(1);
+        // advance the recurrence before anything else can fail, so it is not created twice
+        recurrences.recurrences[index].task_id = task.id;
+        recurrences.recurrences[index].due = due;
+        save_recurrences(&recurrences);
+        index += 1;
+
+        let due_date = Some(due.format("%Y-%m-%d").to_string());
+        match taiga.due_task(task.id, due_date, task.version) {
+            Ok(task) => spawned.push(task),
+            Err(err) => {
+                eprintln!(
+                    "Warning, could not set the due date of {}: {}",
+                    recurrence.name, err
+                );
+                spawned.push(task);
+            }
         }
-    } else if assigned.iter().any(|m| *m == member_id) {
-        eprintln!("Error, the user is already assigned to the task, cannot add");
-        exit(1);
-    } else {
-        assigned.push(member_id);
     }
 
-    // pushing the change
-    if let Ok(new_task) = taiga.assign_task(task.id, assigned, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not assign task");
-        exit(1);
-    }
+    spawned
 }
 
-pub fn taiga_due(taiga: &mut Taiga, args: DueTaskArgs) {
-    // getting necessary information
+pub fn taiga_import_stories(taiga: &mut Taiga, args: ImportStoriesArgs) {
+    let input = read_input(&args.path);
+
+    let mut failures = Vec::new();
+    let mut stories = Vec::new();
+    for story in parse_stories(&input, args.format) {
+        match story {
+            Ok(story) => stories.push(story),
+            Err(failure) => failures.push(failure),
+        }
+    }
+
     let project = taiga.find_project(args.project).unwrap_or_else(|err| {
         eprintln!("{}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
-    let task = tasks.get_task(args.id);
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        stories.iter().any(|story| {
+            story
+                .status
+                .as_ref()
+                .is_some_and(|status| !tasks.statuses.iter().any(|s| s.slug == *status))
+                || story
+                    .assign
+                    .iter()
+                    .any(|username| member_outdated(&tasks.members, username))
+        })
+    });
 
-    // pushing the change
-    if let Ok(new_task) = taiga.due_task(task.id, args.due_date, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not assign task");
-        exit(1);
+    // resolving the statuses, users and dates of every story
+    let mut resolved: Vec<(ImportStory, i32, Vec<i32>, Option<String>)> = Vec::new();
+    for story in stories {
+        let status = if let Some(status) = &story.status {
+            tasks.statuses.iter().find(|s| s.slug == *status)
+        } else if story.done {
+            tasks.statuses.iter().find(|s| s.is_closed)
+        } else {
+            tasks.statuses.first()
+        };
+        let status_id = match status {
+            Some(status) => status.id,
+            None => {
+                failures.push((story.line, "could not find status".to_string()));
+                continue;
+            }
+        };
+
+        let mut assigned_ids = Vec::new();
+        let mut unknown = None;
+        for username in &story.assign {
+            if username == "me" && tasks.members.iter().any(|m| m.id == taiga.id) {
+                assigned_ids.push(taiga.id);
+                continue;
+            }
+            match find_member(&tasks.members, username) {
+                Ok(member) => assigned_ids.push(member.id),
+                Err(err) => {
+                    unknown = Some(err.to_string());
+                    break;
+                }
+            }
+        }
+        if let Some(reason) = unknown {
+            failures.push((story.line, reason));
+            continue;
+        }
+
+        let due_date = match &story.due {
+            Some(due) => match temporis::parse_date(due) {
+                Ok(date) => Some(date.format("%Y-%m-%d").to_string()),
+                Err(_) => {
+                    failures.push((story.line, format!("could not parse due date {}", due)));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        resolved.push((story, status_id, assigned_ids, due_date));
+    }
+
+    // creating the stories, one request per status
+    let mut status_ids: Vec<i32> = resolved.iter().map(|(_, id, _, _)| *id).collect();
+    status_ids.sort();
+    status_ids.dedup();
+
+    let mut created = 0;
+    for status_id in status_ids {
+        let group: Vec<&(ImportStory, i32, Vec<i32>, Option<String>)> = resolved
+            .iter()
+            .filter(|(_, id, _, _)| *id == status_id)
+            .collect();
+        let names: Vec<String> = group
+            .iter()
+            .map(|(story, ..)| story.subject.clone())
+            .collect();
+
+        let new_tasks = match taiga.bulk_new_tasks(project.id, status_id, names.clone()) {
+            Ok(new_tasks) => new_tasks,
+            Err(err) => {
+                for (story, ..) in &group {
+                    failures.push((story.line, format!("could not create story: {}", err)));
+                }
+                continue;
+            }
+        };
+
+        let (new_tasks, unmatched) = match_created(&names, new_tasks, |task| &task.name);
+        for task in unmatched {
+            eprintln!(
+                "Warning, created #{} {} without a line to match",
+                task.reference, task.name
+            );
+            created += 1;
+            tasks.tasks.push(task);
+        }
+
+        for ((story, _, assigned_ids, due_date), new_task) in group.into_iter().zip(new_tasks) {
+            let mut new_task = match new_task {
+                Some(new_task) => new_task,
+                None => {
+                    failures.push((story.line, "not among the created stories".to_string()));
+                    continue;
+                }
+            };
+            created += 1;
+
+            if !assigned_ids.is_empty() || due_date.is_some() {
+                match taiga.modify_task(
+                    new_task.id,
+                    new_task.status_id,
+                    new_task.name.clone(),
+                    assigned_ids.clone(),
+                    due_date.clone(),
+                    new_task.team,
+                    new_task.client,
+                    new_task.blocked,
+                    new_task.version,
+                ) {
+                    Ok(mod_task) => new_task = mod_task,
+                    Err(err) => failures.push((
+                        story.line,
+                        format!("created but could not assign or set due date: {}", err),
+                    )),
+                }
+            }
+
+            if !story.tags.is_empty() {
+                match taiga.detail_task(
+                    new_task.id,
+                    None,
+                    Some(story.tags.clone()),
+                    HashMap::new(),
+                    new_task.version,
+                ) {
+                    Ok(detail_task) => new_task = detail_task,
+                    Err(err) => failures.push((
+                        story.line,
+                        format!("created but could not set tags: {}", err),
+                    )),
+                }
+            }
+
+            tasks.tasks.push(new_task);
+        }
+    }
+    tasks.save_cache();
+
+    println!("Created {} stories", created);
+    if !failures.is_empty() {
+        failures.sort();
+        for (line, reason) in failures {
+            eprintln!("Error, line {}: {}", line, reason);
+        }
+        exit(1);
+    }
+}
+
+pub fn taiga_export(taiga: &mut Taiga, args: ProjectExportArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get project: {}", err);
+        exit(1);
+    });
+    let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
+        exit(1);
+    });
+
+    let output = match args.format {
+        ExportFormat::Taskwarrior => {
+            let uuids = TaigaUuids::from_cache(project.id);
+            let project_slug = slug(project.name.clone());
+            let exported: Vec<TaskwarriorTask> = tasks
+                .iter()
+                .map(|task| {
+                    let uuid = uuids
+                        .uuids
+                        .get(&task.reference)
+                        .cloned()
+                        .unwrap_or_else(|| story_uuid(project.id, task.reference));
+                    TaskwarriorTask::from_task(
+                        task,
+                        uuid,
+                        &project_slug,
+                        &project.statuses,
+                        &project.members,
+                    )
+                })
+                .collect();
+            serde_json::to_string_pretty(&exported)
+        }
+        ExportFormat::Json => {
+            let archive = taiga.export_archive(project.id).unwrap_or_else(|err| {
+                eprintln!("Error, could not export project: {}", err);
+                exit(1);
+            });
+            serde_json::to_string_pretty(&archive)
+        }
+    };
+
+    println!(
+        "{}",
+        output.unwrap_or_else(|err| {
+            eprintln!("Error, could not serialize tasks: {}", err);
+            exit(1);
+        })
+    );
+}
+
+// Create or update stories from taskwarrior tasks, printing them back with their Taiga ref
+pub fn taiga_import(taiga: &mut Taiga, args: ProjectImportArgs) {
+    let input = read_input(&args.path);
+    let mut imported = parse_tasks(&input).unwrap_or_else(|err| {
+        eprintln!("Error, could not parse taskwarrior tasks: {}", err);
+        exit(1);
+    });
+
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get project: {}", err);
+        exit(1);
+    });
+    let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
+        exit(1);
+    });
+
+    let mut uuids = TaigaUuids::from_cache(project.id);
+    let mut changed: Vec<TaigaTask> = Vec::new();
+    let mut failures = Vec::new();
+    let mut created = 0;
+    let mut updated = 0;
+
+    for task in imported.iter_mut().filter(|task| !task.deleted()) {
+        // a task imported before without its ref is found again through its uuid
+        let reference = task.taigaref.or_else(|| {
+            uuids
+                .uuids
+                .iter()
+                .find(|(reference, uuid)| {
+                    **uuid == task.uuid && tasks.iter().any(|t| t.reference == **reference)
+                })
+                .map(|(reference, _)| *reference)
+        });
+        let existing = match reference {
+            Some(reference) => match tasks.iter().find(|t| t.reference == reference) {
+                Some(existing) => Some(existing),
+                None => {
+                    failures.push((
+                        task.description.clone(),
+                        format!("story #{} not found", reference),
+                    ));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        // keeping the Taiga status as long as it agrees with the taskwarrior status
+        let wanted = task
+            .taigastatus
+            .as_ref()
+            .and_then(|status| project.statuses.iter().find(|s| s.slug == *status));
+        let current = existing.and_then(|t| project.statuses.iter().find(|s| s.id == t.status_id));
+        let status = match [wanted, current]
+            .into_iter()
+            .flatten()
+            .chain(project.statuses.iter())
+            .find(|status| status.is_closed == task.completed())
+        {
+            Some(status) => status,
+            None => {
+                failures.push((
+                    task.description.clone(),
+                    "could not find status".to_string(),
+                ));
+                continue;
+            }
+        };
+
+        let assigned_ids = match task
+            .assigned()
+            .iter()
+            .map(|username| find_member(&project.members, username).map(|member| member.id))
+            .collect::<Result<Vec<i32>>>()
+        {
+            Ok(ids) => ids,
+            Err(err) => {
+                failures.push((task.description.clone(), err.to_string()));
+                continue;
+            }
+        };
+
+        let due = match task.due_date().transpose() {
+            Ok(due) => due,
+            Err(err) => {
+                failures.push((task.description.clone(), err));
+                continue;
+            }
+        };
+        let due_date = due.map(|due| due.format("%Y-%m-%d").to_string());
+
+        let result = match existing {
+            Some(existing) => {
+                let mut existing_assigned = existing.assigned.clone();
+                existing_assigned.sort();
+                let mut sorted_ids = assigned_ids.clone();
+                sorted_ids.sort();
+
+                if existing.name == task.description
+                    && existing.status_id == status.id
+                    && existing_assigned == sorted_ids
+                    && existing.due.map(|due| due.date_naive()) == due
+                {
+                    task.taigaref = Some(existing.reference);
+                    uuids.uuids.insert(existing.reference, task.uuid.clone());
+                    continue;
+                }
+
+                updated += 1;
+                taiga.modify_task(
+                    existing.id,
+                    status.id,
+                    task.description.clone(),
+                    assigned_ids,
+                    due_date,
+                    existing.team,
+                    existing.client,
+                    existing.blocked,
+                    existing.version,
+                )
+            }
+            None => {
+                created += 1;
+                taiga
+                    .new_task(
+                        project.id,
+                        status.id,
+                        task.description.clone(),
+                        assigned_ids.clone(),
+                        false,
+                        false,
+                        false,
+                    )
+                    .and_then(|new_task| {
+                        if due_date.is_none() && assigned_ids.len() <= 1 {
+                            return Ok(new_task);
+                        }
+                        taiga.modify_task(
+                            new_task.id,
+                            new_task.status_id,
+                            new_task.name.clone(),
+                            assigned_ids,
+                            due_date,
+                            new_task.team,
+                            new_task.client,
+                            new_task.blocked,
+                            new_task.version,
+                        )
+                    })
+            }
+        };
+
+        match result {
+            Ok(story) => {
+                task.taigaref = Some(story.reference);
+                task.taigastatus = Some(story.status.clone());
+                uuids.uuids.insert(story.reference, task.uuid.clone());
+                changed.push(story);
+            }
+            Err(err) => failures.push((task.description.clone(), err.to_string())),
+        }
+    }
+    uuids.save_cache();
+
+    if let Some(mut cached) = TaigaTasks::from_cache(project.id) {
+        for story in changed {
+            match cached.tasks.iter_mut().find(|t| t.id == story.id) {
+                Some(cached_task) => *cached_task = story,
+                None => cached.tasks.push(story),
+            }
+        }
+        cached.save_cache();
+    }
+
+    match serde_json::to_string_pretty(&imported) {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            eprintln!("Error, could not serialize tasks: {}", err);
+            exit(1);
+        }
+    }
+
+    eprintln!("Created {} stories, updated {} stories", created, updated);
+    if !failures.is_empty() {
+        for (description, reason) in failures {
+            eprintln!("Error, {}: {}", description, reason);
+        }
+        exit(1);
+    }
+}
+
+// Push the edits made to a board file since the last sync, then rewrite it from Taiga
+pub fn taiga_sync(taiga: &mut Taiga, args: ProjectSyncArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get project: {}", err);
+        exit(1);
+    });
+    let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
+        exit(1);
+    });
+
+    let path = fs::canonicalize(&args.path)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| args.path.clone());
+    let state = TaigaSyncState::from_cache(project.id, &path);
+
+    let items = match fs::read_to_string(&args.path) {
+        Ok(input) => parse_board(&input).unwrap_or_else(|(line, reason)| {
+            eprintln!("Error, line {}: {}", line, reason);
+            exit(1);
+        }),
+        // the first sync creates the file
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => {
+            eprintln!("Error, could not read {}: {}", args.path, err);
+            exit(1);
+        }
+    };
+
+    let mut failures = false;
+    for item in items {
+        let heading = match project.statuses.iter().find(|s| s.slug == item.status) {
+            Some(heading) => heading,
+            None => {
+                eprintln!("Error, line {}: unknown status {}", item.line, item.status);
+                failures = true;
+                continue;
+            }
+        };
+
+        // a checkbox that disagrees with its heading opens or closes the task
+        let status = if item.checked == heading.is_closed {
+            Some(heading)
+        } else {
+            project
+                .statuses
+                .iter()
+                .find(|s| s.is_closed == item.checked)
+        };
+        let status = match status {
+            Some(status) => status,
+            None => {
+                eprintln!("Error, line {}: could not find status", item.line);
+                failures = true;
+                continue;
+            }
+        };
+
+        let reference = match item.reference {
+            Some(reference) => reference,
+            None => {
+                match taiga.new_task(
+                    project.id,
+                    status.id,
+                    item.name.clone(),
+                    Vec::new(),
+                    false,
+                    false,
+                    false,
+                ) {
+                    Ok(task) => println!("Created {}", task.name),
+                    Err(err) => {
+                        eprintln!("Error, could not create {}: {}", item.name, err);
+                        failures = true;
+                    }
+                }
+                continue;
+            }
+        };
+
+        let remote = match tasks.iter().find(|task| task.reference == reference) {
+            Some(remote) => remote,
+            None => {
+                eprintln!("Warning, #{} no longer exists on Taiga", reference);
+                continue;
+            }
+        };
+
+        let synced = state.stories.iter().find(|s| s.reference == reference);
+        let (file_changed, remote_changed) = match synced {
+            Some(synced) => (
+                item.name != synced.name || status.id != synced.status_id,
+                remote.version != synced.version,
+            ),
+            None => (
+                item.name != remote.name || status.id != remote.status_id,
+                false,
+            ),
+        };
+
+        if !file_changed {
+            continue;
+        }
+        if remote_changed {
+            eprintln!(
+                "Warning, #{} changed on both sides, keeping the Taiga version",
+                reference
+            );
+            continue;
+        }
+
+        let mut version = remote.version;
+        if item.name != remote.name {
+            match taiga.rename_task(remote.id, item.name.clone(), version) {
+                Ok(task) => {
+                    println!("Renamed {} to {}", remote.name, task.name);
+                    version = task.version;
+                }
+                Err(err) => {
+                    eprintln!("Error, could not rename {}: {}", remote.name, err);
+                    failures = true;
+                    continue;
+                }
+            }
+        }
+        if status.id != remote.status_id {
+            match taiga.move_task(remote.id, status.id, version) {
+                Ok(task) => println!("Moved {} to {}", task.name, task.status),
+                Err(err) => {
+                    eprintln!("Error, could not move {}: {}", item.name, err);
+                    failures = true;
+                }
+            }
+        }
+    }
+
+    // pulling every change back, including the ones just pushed
+    let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get tasks: {}", err);
+        exit(1);
+    });
+
+    fs::write(
+        &args.path,
+        render_board(&project.name, &project.statuses, &tasks),
+    )
+    .unwrap_or_else(|err| {
+        eprintln!("Error, could not write {}: {}", args.path, err);
+        exit(1);
+    });
+
+    let path = fs::canonicalize(&args.path)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or(path);
+    TaigaSyncState {
+        id: project.id,
+        path,
+        stories: tasks
+            .iter()
+            .map(|task| TaigaSyncStory {
+                reference: task.reference,
+                name: task.name.clone(),
+                status_id: task.status_id,
+                version: task.version,
+            })
+            .collect(),
+    }
+    .save_cache();
+
+    if let Some(mut cached) = TaigaTasks::from_cache(project.id) {
+        for cached_task in cached.tasks.iter_mut() {
+            if let Some(task) = tasks.iter().find(|task| task.id == cached_task.id) {
+                *cached_task = task.clone();
+            }
+        }
+        cached.save_cache();
+    }
+
+    if failures {
+        exit(1);
+    }
+}
+
+// Serve the signed webhook payloads of Taiga, applying them to the cached tasks
+pub fn taiga_webhook_serve(taiga: &mut Taiga, args: WebhookServeArgs) {
+    let listener = TcpListener::bind((args.host.as_str(), args.port)).unwrap_or_else(|err| {
+        eprintln!(
+            "Error, could not listen on {}:{}: {}",
+            args.host, args.port, err
+        );
+        exit(1);
+    });
+    println!("Listening on {}:{}", args.host, args.port);
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Error, could not accept connection: {}", err);
+                continue;
+            }
+        };
+
+        let request = match read_request(&stream) {
+            Ok(request) => request,
+            Err((code, err)) => {
+                respond(&stream, code);
+                eprintln!("Error, invalid request: {}", err);
+                continue;
+            }
+        };
+
+        if !verify_signature(&args.key, &request.body, request.signature.as_deref()) {
+            respond(&stream, 401);
+            eprintln!("Error, rejected a request with an invalid signature");
+            continue;
+        }
+
+        let body = String::from_utf8_lossy(&request.body).to_string();
+        match taiga.apply_webhook(&body) {
+            Ok(event) => {
+                respond(&stream, 200);
+                if let Some(event) = event {
+                    println!("{} #{} {}", event.action, event.reference, event.name);
+                }
+            }
+            Err(err) => {
+                respond(&stream, 400);
+                eprintln!("Error, could not apply webhook: {}", err);
+                continue;
+            }
+        }
+
+        // hooks run after answering so that a slow hook does not time out the delivery
+        for hook in find_hooks("on-webhook") {
+            if let Err(err) = run_hook(&hook, &body) {
+                eprintln!("Error, {}", err);
+            }
+        }
+    }
+}
+
+// Write the tasks with a due date of one or all projects as a calendar
+pub fn taiga_ics(taiga: &mut Taiga, args: IcsArgs) {
+    let projects = match args.project {
+        Some(project) => vec![taiga.find_project(project).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            exit(1);
+        })],
+        None => taiga.projects.clone(),
+    };
+
+    let web_url = taiga.web_url();
+    let mut entries = Vec::new();
+    for project in projects {
+        let project_slug = taiga.get_project_slug(project.id).unwrap_or_else(|err| {
+            eprintln!("Error, could not get project: {}", err);
+            exit(1);
+        });
+        let project = taiga.get_project(project.id).unwrap_or_else(|err| {
+            eprintln!("Error, could not get project: {}", err);
+            exit(1);
+        });
+        let tasks = taiga.get_tasks(project.id).unwrap_or_else(|err| {
+            eprintln!("Error, could not get tasks: {}", err);
+            exit(1);
+        });
+
+        for task in tasks {
+            let due = match task.due {
+                Some(due) => due.date_naive(),
+                None => continue,
+            };
+
+            entries.push(CalendarEntry {
+                uid: format!("taiga-{}-{}@taiga-cli", project.id, task.reference),
+                summary: format!("#{} {}", task.reference, task.name),
+                due,
+                project: project.name.clone(),
+                status: task.status.clone(),
+                assigned: task
+                    .assigned
+                    .iter()
+                    .filter_map(|id| project.members.iter().find(|m| m.id == *id))
+                    .map(|member| member.username.clone())
+                    .collect(),
+                url: format!("{}/project/{}/us/{}", web_url, project_slug, task.reference),
+                closed: task.closed,
+            });
+        }
+    }
+
+    let calendar = render_calendar(&entries, args.events, Utc::now());
+    match &args.path {
+        Some(path) => {
+            fs::write(path, calendar).unwrap_or_else(|err| {
+                eprintln!("Error, could not write {}: {}", path, err);
+                exit(1);
+            });
+            println!("Wrote {} tasks to {}", entries.len(), path);
+        }
+        None => print!("{}", calendar),
+    }
+}
+
+pub fn taiga_restore(taiga: &mut Taiga, args: ProjectRestoreArgs) {
+    let input = read_input(&Some(args.path.clone()));
+    let archive: ProjectArchive = serde_json::from_str(&input).unwrap_or_else(|err| {
+        eprintln!("Error, could not parse {}: {}", args.path, err);
+        exit(1);
+    });
+
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let report = taiga
+        .restore_archive(project.id, &archive)
+        .unwrap_or_else(|err| {
+            eprintln!("Error, could not restore archive: {}", err);
+            exit(1);
+        });
+
+    println!(
+        "Restored {} stories, {} comments, {} epics and {} milestones, created {} statuses",
+        report.stories, report.comments, report.epics, report.milestones, report.statuses
+    );
+    if !report.missing_members.is_empty() {
+        eprintln!(
+            "Warning, not members of the project, left unassigned: {}",
+            report.missing_members.join(", ")
+        );
+    }
+    if report.skipped_attachments > 0 {
+        eprintln!(
+            "Warning, {} attachments were not restored, their urls are in the archive",
+            report.skipped_attachments
+        );
+    }
+}
+
+// Read the whole file, or stdin when there is no file
+fn read_input(path: &Option<String>) -> String {
+    match path {
+        Some(path) => fs::read_to_string(path).unwrap_or_else(|err| {
+            eprintln!("Error, could not read {}: {}", path, err);
+            exit(1);
+        }),
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .unwrap_or_else(|err| {
+                    eprintln!("Error, could not read stdin: {}", err);
+                    exit(1);
+                });
+            input
+        }
+    }
+}
+
+pub fn taiga_move(taiga: &mut Taiga, config: &TaigaConfig, args: MoveTaskArgs) {
+    // getting the necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        !tasks
+            .statuses
+            .iter()
+            .any(|status| status.slug == args.status)
+            || selector_outdated(tasks, &args.selector)
+    });
+
+    // checking status is present on the project
+    let status_id = if let Some(status) = tasks
+        .statuses
+        .iter()
+        .find(|status| status.slug == args.status)
+    {
+        status.id
+    } else {
+        eprintln!("Error, could not find given status");
+        exit(1);
+    };
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    check_wip_limit(taiga, config, &tasks, status_id, &selected);
+    confirm_tasks(config, "move", selected.len());
+
+    // pushing the changes
+    move_tasks(taiga, &mut tasks, selected, status_id, "move");
+}
+
+pub fn taiga_delete(taiga: &mut Taiga, config: &TaigaConfig, args: DeleteTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        tasks.statuses.is_empty() || selector_outdated(tasks, &args.selector)
+    });
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "delete", selected.len());
+
+    apply_cached_tasks(
+        taiga,
+        &mut tasks,
+        selected,
+        "delete",
+        true,
+        |taiga, task| {
+            run_pre_hooks("delete", Some(task), None)?;
+            taiga.delete_task(task.id)?;
+            run_post_hooks("delete", Some(task), None);
+            Ok(task.clone())
+        },
+    );
+}
+
+pub fn taiga_attach(taiga: &mut Taiga, config: &TaigaConfig, args: AttachTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "attach to", selected.len());
+
+    apply_tasks(taiga, &mut tasks, selected, "attach", |taiga, task| {
+        taiga
+            .upload_attachment(project.id, task.id, &args.path)
+            .and_then(|_| taiga.get_task(task.id))
+    });
+}
+
+pub fn taiga_attachments(taiga: &mut Taiga, args: ListAttachmentsArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| tasks.members.is_empty());
+    let task = select_tasks(taiga, &mut tasks, &args.selector).remove(0);
+
+    let attachments = taiga
+        .get_attachments(project.id, task.id)
+        .unwrap_or_else(|err| {
+            eprintln!("Error, could not get attachments: {}", err);
+            exit(1);
+        });
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT_CLEAN);
+    table.add_row(row!["#", "NAME", "SIZE", "AUTHOR", "DATE"]);
+    for (i, attachment) in attachments.iter().enumerate() {
+        let author = attachment
+            .owner
+            .and_then(|owner| tasks.members.iter().find(|member| member.id == owner))
+            .map(|member| member.username.clone())
+            .unwrap_or_default();
+        table.add_row(Row::new(vec![
+            Cell::new(&(i + 1).to_string()),
+            Cell::new(&attachment.name),
+            Cell::new(&format_size(attachment.size)),
+            Cell::new(&author),
+            Cell::new(attachment.created.get(..10).unwrap_or(&attachment.created)),
+        ]));
+    }
+    table.printstd();
+}
+
+pub fn taiga_attachment_get(taiga: &mut Taiga, args: GetAttachmentArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
+    let task = select_tasks(taiga, &mut tasks, &args.selector).remove(0);
+
+    let attachments = taiga
+        .get_attachments(project.id, task.id)
+        .unwrap_or_else(|err| {
+            eprintln!("Error, could not get attachments: {}", err);
+            exit(1);
+        });
+    let attachment = attachments.get(args.index - 1).unwrap_or_else(|| {
+        eprintln!("Invalid attachment for this task");
+        exit(1);
+    });
+
+    // the name comes from the server, so only its last component is trusted
+    let path = args.output.unwrap_or_else(|| {
+        Path::new(&attachment.name)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| {
+                eprintln!(
+                    "Error, {} is not a file name, give a path to save it to",
+                    attachment.name
+                );
+                exit(1);
+            })
+    });
+    if fs::metadata(&path).is_ok() {
+        eprintln!("Error, {} already exists", path);
+        exit(1);
+    }
+
+    let mut file = fs::File::create(&path).unwrap_or_else(|err| {
+        eprintln!("Error, could not create {}: {}", path, err);
+        exit(1);
+    });
+    match taiga.download_attachment(attachment, &mut file) {
+        Ok(size) => println!("Saved {} ({})", path, format_size(size as i64)),
+        Err(err) => {
+            let _ = fs::remove_file(&path);
+            eprintln!("Error, could not download {}: {}", attachment.name, err);
+            exit(1);
+        }
+    }
+}
+
+pub fn taiga_watch(taiga: &mut Taiga, config: &TaigaConfig, args: WatchTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let action = if args.remove { "unwatch" } else { "watch" };
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, action, selected.len());
+
+    apply_tasks(taiga, &mut tasks, selected, action, |taiga, task| {
+        taiga.watch_task(task.id, args.remove)
+    });
+}
+
+pub fn taiga_vote(taiga: &mut Taiga, config: &TaigaConfig, args: VoteTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let action = if args.remove { "downvote" } else { "upvote" };
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, action, selected.len());
+
+    apply_tasks(taiga, &mut tasks, selected, action, |taiga, task| {
+        taiga.vote_task(task.id, args.remove)
+    });
+}
+
+pub fn taiga_show(taiga: &mut Taiga, args: ShowTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| tasks.members.is_empty());
+    let cached = select_tasks(taiga, &mut tasks, &args.selector).remove(0);
+
+    let (mut task, description) = taiga.get_task_description(cached.id).unwrap_or_else(|err| {
+        eprintln!("Error, could not get task: {}", err);
+        exit(1);
+    });
+    if !tasks.attributes.is_empty() {
+        task.attributes = taiga.get_attribute_values(task.id).unwrap_or_else(|err| {
+            eprintln!("Error, could not get attributes: {}", err);
+            exit(1);
+        });
+    }
+
+    let usernames = |ids: &[i32]| {
+        ids.iter()
+            .map(|id| {
+                tasks
+                    .members
+                    .iter()
+                    .find(|member| member.id == *id)
+                    .map(|member| member.username.clone())
+                    .unwrap_or_else(|| id.to_string())
+            })
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    let mut flags = Vec::new();
+    if task.team {
+        flags.push("team");
+    }
+    if task.client {
+        flags.push("client");
+    }
+    if task.blocked {
+        flags.push("blocked");
+    }
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT_CLEAN);
+    table.add_row(row!["Status", task.status]);
+    table.add_row(row!["Assigned", usernames(&task.assigned)]);
+    if let Some(due) = &task.due {
+        table.add_row(row![
+            "Due",
+            format!("{} ({})", format_due(due), due.format("%Y-%m-%d"))
+        ]);
+    }
+    table.add_row(row!["Flags", flags.join(", ")]);
+    table.add_row(row!["Tags", task.tags.join(", ")]);
+    table.add_row(row!["Watchers", usernames(&task.watchers)]);
+    table.add_row(row!["Votes", task.votes]);
+    for attribute in &tasks.attributes {
+        if let Some(value) = task.attributes.get(&attribute.id) {
+            table.add_row(row![attribute.name, value]);
+        }
+    }
+
+    println!("#{} {}", task.reference, task.name);
+    table.printstd();
+    if !description.trim().is_empty() {
+        println!("\n{}", description.trim());
+    }
+
+    if let Some(cached) = tasks.tasks.iter_mut().find(|t| t.id == task.id) {
+        *cached = task;
+    }
+    tasks.save_cache();
+}
+
+pub fn taiga_comment(taiga: &mut Taiga, config: &TaigaConfig, args: CommentTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "comment on", selected.len());
+
+    apply_tasks(taiga, &mut tasks, selected, "comment", |taiga, task| {
+        taiga.comment_task(task.id, args.text.clone(), task.version)
+    });
+}
+
+pub fn taiga_set(taiga: &mut Taiga, config: &TaigaConfig, args: SetAttributeArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        selector_outdated(tasks, &args.selector)
+            || args
+                .values
+                .iter()
+                .any(|(name, _)| find_attribute(&tasks.attributes, name).is_none())
+    });
+
+    let mut values = Vec::new();
+    for (name, value) in &args.values {
+        let attribute = find_attribute(&tasks.attributes, name).unwrap_or_else(|| {
+            eprintln!("Error, could not find attribute {}", name);
+            exit(1);
+        });
+        let value = attribute.parse_value(value).unwrap_or_else(|err| {
+            eprintln!("Error, invalid value for {}: {}", attribute.name, err);
+            exit(1);
+        });
+        values.push((attribute.id, value));
+    }
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "set attributes of", selected.len());
+
+    apply_tasks(taiga, &mut tasks, selected, "set", |taiga, task| {
+        let attributes = taiga.set_attribute_values(task.id, values.clone())?;
+        Ok(TaigaTask {
+            attributes,
+            ..task.clone()
+        })
+    });
+}
+
+pub fn taiga_branch(taiga: &mut Taiga, config: &TaigaConfig, args: BranchTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let user_id = taiga.id;
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        tasks.statuses.is_empty() || !tasks.members.iter().any(|m| m.id == user_id)
+    });
+    let task = select_tasks(taiga, &mut tasks, &args.selector).remove(0);
+
+    if !args.start {
+        checkout_task_branch(&task);
+        return;
+    }
+
+    let status = tasks
+        .statuses
+        .iter()
+        .find(|status| status.slug == "in-progress")
+        .or_else(|| {
+            tasks
+                .statuses
+                .iter()
+                .find(|status| status.slug.contains("progress"))
+        })
+        .cloned()
+        .unwrap_or_else(|| {
+            eprintln!("Error, could not find an in progress status");
+            exit(1);
+        });
+    if !tasks.members.iter().any(|member| member.id == taiga.id) {
+        eprintln!("Could not find your username on the project");
+        exit(1);
+    }
+    check_wip_limit(
+        taiga,
+        config,
+        &tasks,
+        status.id,
+        std::slice::from_ref(&task),
+    );
+
+    // starting a task outside of a repository only skips the branch
+    if in_repository() {
+        checkout_task_branch(&task);
+    }
+
+    let mut new = TaigaTask {
+        status_id: status.id,
+        status: status.slug,
+        ..task.clone()
+    };
+    if !new.assigned.contains(&taiga.id) {
+        new.assigned.push(taiga.id);
+    }
+
+    // the timer is only started on a story that was started
+    let started = hooked_update(taiga, "modify", &task, new).unwrap_or_else(|err| {
+        eprintln!("Error, could not start task: {}", err);
+        exit(1);
+    });
+    if let Some(cached) = tasks.tasks.iter_mut().find(|t| t.id == started.id) {
+        *cached = started;
+    }
+    tasks.save_cache();
+
+    let now = Utc::now();
+    let mut log = load_timelog();
+    if let Some(timer) = log.timer.take() {
+        let entry = timer.stop(now, None);
+        println!(
+            "Stopped #{} {} after {}",
+            entry.reference,
+            entry.name,
+            format_minutes(entry.minutes)
+        );
+        log.entries.push(entry);
+    }
+    log.timer = Some(TaigaTimer {
+        project_id: project.id,
+        project: project.name,
+        task_id: task.id,
+        reference: task.reference,
+        name: task.name.clone(),
+        started: now,
+    });
+    save_timelog(&log);
+
+    println!("Started a timer on #{} {}", task.reference, task.name);
+}
+
+fn checkout_task_branch(task: &TaigaTask) {
+    let name = branch_name(task.reference, &task.name);
+    match checkout_branch(&name) {
+        Ok(true) => println!("Switched to a new branch {}", name),
+        Ok(false) => println!("Switched to branch {}", name),
+        Err(err) => {
+            eprintln!("Error, could not check out branch: {}", err);
+            exit(1);
+        }
+    }
+}
+
+pub fn taiga_stop(taiga: &mut Taiga, args: StopTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
+    let task = select_tasks(taiga, &mut tasks, &args.selector).remove(0);
+
+    let mut log = load_timelog();
+    let timer = match log.timer.take() {
+        Some(timer) if timer.task_id == task.id => timer,
+        _ => {
+            eprintln!("Error, there is no timer running on #{}", task.reference);
+            exit(1);
+        }
+    };
+
+    let entry = timer.stop(Utc::now(), args.note);
+    println!(
+        "Logged {} on #{} {}",
+        format_minutes(entry.minutes),
+        entry.reference,
+        entry.name
+    );
+    log.entries.push(entry);
+    save_timelog(&log);
+}
+
+pub fn taiga_log(taiga: &mut Taiga, args: LogTimeArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
+    let task = select_tasks(taiga, &mut tasks, &args.selector).remove(0);
+
+    let mut log = load_timelog();
+    log.entries.push(TaigaTimeEntry {
+        project_id: project.id,
+        project: project.name,
+        task_id: task.id,
+        reference: task.reference,
+        name: task.name.clone(),
+        started: Utc::now() - Duration::minutes(args.minutes),
+        minutes: args.minutes,
+        note: args.note,
+    });
+    save_timelog(&log);
+
+    println!(
+        "Logged {} on #{} {}",
+        format_minutes(args.minutes),
+        task.reference,
+        task.name
+    );
+}
+
+pub fn taiga_timesheet(taiga: &mut Taiga, args: TimesheetArgs) {
+    let project_id = args.project.map(|project| {
+        taiga
+            .find_project(project)
+            .unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                exit(1);
+            })
+            .id
+    });
+
+    let today = Local::now().date_naive();
+    let first_day = if args.week {
+        today - Duration::days(today.weekday().num_days_from_monday() as i64)
+    } else {
+        today
+    };
+    let since = first_day
+        .and_hms_opt(0, 0, 0)
+        .and_then(|start| start.and_local_timezone(Local).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .expect("Could not get the start of the period");
+
+    let log = load_timelog();
+    let entries: Vec<&TaigaTimeEntry> = log
+        .entries
+        .iter()
+        .filter(|entry| entry.started >= since)
+        .filter(|entry| project_id.is_none_or(|id| entry.project_id == id))
+        .collect();
+
+    let projects = timesheet(&entries);
+
+    let mut table = Table::new();
+    table.set_format(*FORMAT_CLEAN);
+    for stories in projects.values() {
+        let name = match stories.values().next() {
+            Some((entry, _)) => &entry.project,
+            None => continue,
+        };
+        let total: i64 = stories.values().map(|(_, minutes)| minutes).sum();
+        table.add_row(Row::new(vec![
+            Cell::new(name).style_spec("b"),
+            Cell::new(&format_minutes(total)).style_spec("b"),
+        ]));
+        for (reference, (entry, minutes)) in stories {
+            table.add_row(Row::new(vec![
+                Cell::new(&format!("  #{} {}", reference, entry.name)),
+                Cell::new(&format_minutes(*minutes)),
+            ]));
+        }
+    }
+    let total: i64 = entries.iter().map(|entry| entry.minutes).sum();
+    table.add_row(Row::new(vec![
+        Cell::new("Total").style_spec("b"),
+        Cell::new(&format_minutes(total)).style_spec("b"),
+    ]));
+    table.printstd();
+
+    if let Some(timer) = &log.timer {
+        if project_id.is_none_or(|id| timer.project_id == id) {
+            let minutes = (Utc::now() - timer.started).num_minutes();
+            println!(
+                "\nRunning: #{} {} for {}",
+                timer.reference,
+                timer.name,
+                format_minutes(minutes)
+            );
+        }
+    }
+
+    let attribute = match args.push {
+        Some(attribute) => attribute,
+        None => return,
+    };
+
+    let mut failed = 0;
+    for (project_id, stories) in &projects {
+        let attributes = taiga.get_attributes(*project_id).unwrap_or_else(|err| {
+            eprintln!("Error, could not get custom attributes: {}", err);
+            exit(1);
+        });
+        let attribute = find_attribute(&attributes, &attribute).unwrap_or_else(|| {
+            eprintln!("Error, could not find the custom attribute {}", attribute);
+            exit(1);
+        });
+
+        // the attribute holds the time spent on the story so far, not only in the period
+        for (entry, _) in stories.values() {
+            let minutes = log.task_minutes(entry.task_id);
+            let value = if attribute.kind == "number" {
+                serde_json::json!((minutes as f64 / 60.0 * 100.0).round() / 100.0)
+            } else {
+                serde_json::json!(format_minutes(minutes))
+            };
+
+            match taiga.set_attribute_values(entry.task_id, vec![(attribute.id, value)]) {
+                Ok(_) => println!("Pushed {} to #{}", format_minutes(minutes), entry.reference),
+                Err(err) => {
+                    eprintln!("Error, could not push #{}: {}", entry.reference, err);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        exit(1);
+    }
+}
+
+// The entries by project id and then by story reference, each story with its total, the
+// project name is only displayed as two projects may share it
+fn timesheet<'a>(
+    entries: &[&'a TaigaTimeEntry],
+) -> BTreeMap<i32, BTreeMap<i32, (&'a TaigaTimeEntry, i64)>> {
+    let mut projects: BTreeMap<i32, BTreeMap<i32, (&TaigaTimeEntry, i64)>> = BTreeMap::new();
+    for entry in entries {
+        let story = projects
+            .entry(entry.project_id)
+            .or_default()
+            .entry(entry.reference)
+            .or_insert((entry, 0));
+        story.1 += entry.minutes;
+    }
+    projects
+}
+
+pub fn taiga_install_hook(args: InstallHookArgs) {
+    let dir = hooks_dir().unwrap_or_else(|err| {
+        eprintln!("Error, could not find the git hooks: {}", err);
+        exit(1);
+    });
+    let path = dir.join("commit-msg");
+
+    // never overwrite a hook that was not installed by us
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !existing.contains(COMMIT_HOOK_MARKER) {
+            eprintln!("Error, {} already exists", path.display());
+            exit(1);
+        }
+    }
+
+    let hook = format!(
+        concat!(
+            "#!/bin/sh\n# {}\n",
+            "taiga '{}' commit-msg \"$1\" || echo 'Warning, taiga could not run' >&2\n",
+            "exit 0\n"
+        ),
+        COMMIT_HOOK_MARKER,
+        args.project.replace('\'', "'\\''")
+    );
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&path, hook))
+        .and_then(|_| make_executable(&path))
+        .unwrap_or_else(|err| {
+            eprintln!("Error, could not write {}: {}", path.display(), err);
+            exit(1);
+        });
+
+    println!("Installed {}", path.display());
+}
+
+#[cfg(unix)]
+fn make_executable(path: &std::path::Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &std::path::Path) -> io::Result<()> {
+    Ok(())
+}
+
+// Reference the story of the branch in the message and apply the `TG-<ref> #status` mentions,
+// without ever failing the commit because of Taiga
+pub fn taiga_commit_msg(args: CommitMsgArgs) {
+    let message = match fs::read_to_string(&args.path) {
+        Ok(message) => message,
+        Err(err) => {
+            eprintln!("Warning, could not read {}: {}", args.path, err);
+            return;
+        }
+    };
+
+    // git drops the comment lines, the message is what comes before them
+    let end = message
+        .lines()
+        .position(|line| line.starts_with('#'))
+        .unwrap_or(message.lines().count());
+    let lines: Vec<&str> = message.lines().collect();
+    let body = lines[..end].join("\n");
+    if body.trim().is_empty() {
+        return;
+    }
+
+    let mut references = commit_references(&body);
+    if let Some(reference) = current_branch().ok().and_then(|b| branch_reference(&b)) {
+        if !references.iter().any(|(r, _)| *r == reference) {
+            let mut updated = format!("{}\n\nTG-{}\n", body.trim_end(), reference);
+            if end < lines.len() {
+                updated.push_str(&lines[end..].join("\n"));
+                updated.push('\n');
+            }
+            if let Err(err) = fs::write(&args.path, updated) {
+                eprintln!("Warning, could not write {}: {}", args.path, err);
+            }
+            references.push((reference, None));
+        }
+    }
+
+    let moves: Vec<(i32, String)> = references
+        .into_iter()
+        .filter_map(|(reference, status)| status.map(|status| (reference, status)))
+        .collect();
+    if moves.is_empty() {
+        return;
+    }
+
+    let (mut taiga, project) = match commit_msg_project(&args.project) {
+        Ok(found) => found,
+        Err(err) => {
+            eprintln!("Warning, {}", err);
+            return;
+        }
+    };
+    let taiga = &mut taiga;
+    let (statuses, stories) = match taiga
+        .get_project(project.id)
+        .and_then(|p| Ok((p.statuses, taiga.get_tasks(project.id)?)))
+    {
+        Ok(result) => result,
+        Err(err) => {
+            eprintln!("Warning, could not get the stories to move: {}", err);
+            return;
+        }
+    };
+
+    for (reference, slug) in moves {
+        let story = stories.iter().find(|story| story.reference == reference);
+        let status = statuses.iter().find(|status| status.slug == slug);
+        let (story, status) = match (story, status) {
+            (Some(story), Some(status)) => (story, status),
+            (None, _) => {
+                eprintln!("Warning, story #{} not found", reference);
+                continue;
+            }
+            (_, None) => {
+                eprintln!("Warning, status {} not found", slug);
+                continue;
+            }
+        };
+
+        let new = TaigaTask {
+            status_id: status.id,
+            status: status.slug.clone(),
+            ..story.clone()
+        };
+        match hooked_update(taiga, "move", story, new) {
+            Ok(task) => println!("Moved #{} {} to {}", task.reference, task.name, status.slug),
+            Err(err) => eprintln!("Warning, could not move #{}: {}", reference, err),
+        }
+    }
+}
+
+// The login and project the hook works with, from the cache only so that nothing is prompted
+fn commit_msg_project(name: &str) -> Result<(Taiga, TaigaProject)> {
+    let taiga = Taiga::from_cache().ok_or_else(|| anyhow!("not logged in, run taiga login"))?;
+    let config = TaigaConfig::load()?;
+    let project = taiga.resolve_project(name, &config)?;
+    Ok((taiga, project))
+}
+
+pub fn taiga_done(taiga: &mut Taiga, config: &TaigaConfig, args: DoneTaskArgs) {
+    // getting the necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        tasks.statuses.is_empty() || selector_outdated(tasks, &args.selector)
+    });
+
+    // getting the appropriate status for done
+    let status_id = if let Some(status) = tasks.statuses.iter().find(|status| status.is_closed) {
+        status.id
+    } else if let Some(status) = tasks.statuses.iter().find(|status| status.slug == "done") {
+        status.id
+    } else {
+        tasks
+            .statuses
+            .last()
+            .expect("No statuses in the project")
+            .id
+    };
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "done", selected.len());
+
+    // pushing the change
+    move_tasks(taiga, &mut tasks, selected, status_id, "done");
+}
+
+pub fn taiga_reopen(taiga: &mut Taiga, config: &TaigaConfig, args: ReopenTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        tasks.statuses.is_empty() || selector_outdated(tasks, &args.selector)
+    });
+
+    let status_id = first_open_status(&tasks.statuses).unwrap_or_else(|| {
+        eprintln!("Error, could not find an open status");
+        exit(1);
+    });
+
+    // a filter only finds closed tasks when asked to
+    let selector = match args.selector {
+        TaskSelector::Filter(filter) => TaskSelector::Filter(TaskFilter {
+            closed: true,
+            ..filter
+        }),
+        selector => selector,
+    };
+
+    let (selected, open): (Vec<TaigaTask>, Vec<TaigaTask>) =
+        select_tasks(taiga, &mut tasks, &selector)
+            .into_iter()
+            .partition(|task| task.closed);
+    for task in &open {
+        eprintln!("Warning, #{} {} is not closed", task.reference, task.name);
+    }
+    if selected.is_empty() {
+        eprintln!("Error, no closed task to reopen");
+        exit(1);
+    }
+    confirm_tasks(config, "reopen", selected.len());
+
+    move_tasks(taiga, &mut tasks, selected, status_id, "reopen");
+}
+
+// The status reopened tasks go to, statuses are kept in the order of the board
+fn first_open_status(statuses: &[TaigaStatus]) -> Option<i32> {
+    statuses
+        .iter()
+        .find(|status| !status.is_closed && !status.is_archived)
+        .map(|status| status.id)
+}
+
+pub fn taiga_rename(taiga: &mut Taiga, config: &TaigaConfig, args: RenameTaskArgs) {
+    // getting necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "rename", selected.len());
+
+    // pushing the change
+    apply_tasks(taiga, &mut tasks, selected, "rename", |taiga, task| {
+        taiga.rename_task(task.id, args.name.clone(), task.version)
+    });
+}
+
+pub fn taiga_assign(taiga: &mut Taiga, config: &TaigaConfig, args: AssignTaskArgs) {
+    // getting necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        member_outdated(&tasks.members, &args.username) || selector_outdated(tasks, &args.selector)
+    });
+
+    let member_id = resolve_member(taiga.id, &tasks.members, &args.username);
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "assign", selected.len());
+
+    // pushing the change
+    apply_tasks(taiga, &mut tasks, selected, "assign", |taiga, task| {
+        // making sure the user can be (de)added from the task
+        let mut assigned = task.assigned.clone();
+        if args.remove {
+            if assigned.contains(&member_id) {
+                assigned.retain(|&m| m != member_id);
+            } else {
+                return Err(anyhow!("the user is not assigned to the task"));
+            }
+        } else if assigned.contains(&member_id) {
+            return Err(anyhow!("the user is already assigned to the task"));
+        } else {
+            assigned.push(member_id);
+        }
+
+        taiga.assign_task(task.id, assigned, task.version)
+    });
+}
+
+pub fn taiga_due(taiga: &mut Taiga, config: &TaigaConfig, args: DueTaskArgs) {
+    // getting necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "set the due date of", selected.len());
+
+    // pushing the change
+    apply_tasks(
+        taiga,
+        &mut tasks,
+        selected,
+        "set the due date of",
+        |taiga, task| taiga.due_task(task.id, args.due_date.clone(), task.version),
+    );
+}
+
+pub fn taiga_team(taiga: &mut Taiga, config: &TaigaConfig, args: TeamTaskArgs) {
+    // getting necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "update", selected.len());
+
+    // pushing the change
+    apply_tasks(taiga, &mut tasks, selected, "update", |taiga, task| {
+        taiga.team_task(task.id, args.remove, task.version)
+    });
+}
+
+pub fn taiga_client(taiga: &mut Taiga, config: &TaigaConfig, args: ClientTaskArgs) {
+    // getting necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "update", selected.len());
+
+    // pushing the change
+    apply_tasks(taiga, &mut tasks, selected, "update", |taiga, task| {
+        taiga.client_task(task.id, args.remove, task.version)
+    });
+}
+
+pub fn taiga_block(taiga: &mut Taiga, config: &TaigaConfig, args: BlockTaskArgs) {
+    // getting necessary information
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks =
+        taiga.tasks_from_cache(project.id, |tasks| selector_outdated(tasks, &args.selector));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "update", selected.len());
+
+    // pushing the change
+    apply_tasks(taiga, &mut tasks, selected, "update", |taiga, task| {
+        taiga.block_task(task.id, args.remove, task.version)
+    });
+}
+
+pub fn taiga_modify(taiga: &mut Taiga, config: &TaigaConfig, args: ModifyTaskArgs) {
+    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        exit(1);
+    });
+    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
+        if let Some(status) = &args.status {
+            if !tasks.statuses.iter().any(|s| s.slug == *status) {
+                return true;
+            }
+        }
+        if let Some(assign) = &args.assign {
+            let replace = assign.replace.iter().flatten();
+            for username in replace.chain(&assign.add).chain(&assign.remove) {
+                if member_outdated(&tasks.members, username) {
+                    return true;
+                }
+            }
+        }
+        selector_outdated(tasks, &args.selector)
+    });
+
+    let mut status_id = 0;
+    if let Some(status) = &args.status {
+        status_id = if let Some(status) = tasks.statuses.iter().find(|s| s.slug == *status) {
+            status.id
+        } else {
+            eprintln!("Error, could not find given status");
+            exit(1);
+        };
+    }
+
+    let resolve_members = |usernames: &[String]| {
+        usernames
+            .iter()
+            .map(|username| resolve_member(taiga.id, &tasks.members, username))
+            .collect::<Vec<i32>>()
+    };
+    let replace_ids = args
+        .assign
+        .as_ref()
+        .and_then(|assign| assign.replace.as_deref())
+        .map(resolve_members);
+    let add_ids = args
+        .assign
+        .as_ref()
+        .map_or_else(Vec::new, |assign| resolve_members(&assign.add));
+    let remove_ids = args
+        .assign
+        .as_ref()
+        .map_or_else(Vec::new, |assign| resolve_members(&assign.remove));
+
+    let selected = select_tasks(taiga, &mut tasks, &args.selector);
+    confirm_tasks(config, "modify", selected.len());
+
+    let status_slug = tasks
+        .statuses
+        .iter()
+        .find(|s| s.id == status_id)
+        .map(|s| s.slug.clone());
+
+    apply_tasks(taiga, &mut tasks, selected, "modify", |taiga, task| {
+        let mut new = task.clone();
+
+        if let Some(slug) = &status_slug {
+            new.status_id = status_id;
+            new.status = slug.clone();
+        }
+        if let Some(rename) = &args.rename {
+            new.name = rename.clone();
+        }
+        if args.assign.is_some() {
+            new.assigned = merge_assignees(
+                &task.assigned,
+                replace_ids.as_deref(),
+                &add_ids,
+                &remove_ids,
+            );
+        }
+        if args.due_date.is_some() {
+            new.due = parse_due_date(&args.due_date);
+        }
+        new.team = args.team.unwrap_or(task.team);
+        new.client = args.client.unwrap_or(task.client);
+        new.blocked = args.block.unwrap_or(task.blocked);
+
+        hooked_update(taiga, "modify", task, new)
+    });
+}
+
+// The assignees of a task after an assign: change, the first assignee is the main one so the
+// given order is kept
+fn merge_assignees(
+    assigned: &[i32],
+    replace: Option<&[i32]>,
+    add: &[i32],
+    remove: &[i32],
+) -> Vec<i32> {
+    let mut merged = replace.unwrap_or(assigned).to_vec();
+    merged.extend(add.iter().copied());
+    merged.retain(|id| !remove.contains(id));
+
+    let mut seen = HashSet::new();
+    merged.retain(|id| seen.insert(*id));
+    merged
+}
+
+// Whether the cached statuses or members are missing something the selector refers to
+fn selector_outdated(tasks: &TaigaTasks, selector: &TaskSelector) -> bool {
+    match selector {
+        TaskSelector::Filter(filter) => filter_outdated(tasks, filter),
+        TaskSelector::Ids(_) | TaskSelector::Reference(_) => false,
+    }
+}
+
+// Resolve the tasks a command applies to, from the cached ids or from the filtered project
+fn select_tasks(
+    taiga: &mut Taiga,
+    tasks: &mut TaigaTasks,
+    selector: &TaskSelector,
+) -> Vec<TaigaTask> {
+    match selector {
+        TaskSelector::Ids(ids) => ids.iter().map(|id| tasks.get_task(*id).clone()).collect(),
+        TaskSelector::Filter(filter) => {
+            let mut project_tasks = tasks.clone();
+            project_tasks.tasks = fetch_tasks(taiga, tasks.id, filter).unwrap_or_else(|err| {
+                eprintln!("Error, could not get tasks: {}", err);
+                exit(1);
+            });
+            keep_attributes(&mut project_tasks.tasks, &tasks.tasks);
+
+            let selected = filter_with_attributes(taiga, &project_tasks, filter);
+            if selected.is_empty() {
+                eprintln!("Error, no task matches the filter");
+                exit(1);
+            }
+            selected
+        }
+        // the story of a branch may not be part of the last search
+        TaskSelector::Reference(reference) => {
+            let task = taiga
+                .get_task_by_ref(tasks.id, *reference)
+                .unwrap_or_else(|err| {
+                    eprintln!("Error, could not get story #{}: {}", reference, err);
+                    exit(1);
+                });
+            vec![task]
+        }
+    }
+}
+
+// Ask for confirmation before changing more tasks than the configured threshold
+fn confirm_tasks(config: &TaigaConfig, action: &str, count: usize) {
+    if count <= config.bulk_confirm {
+        return;
+    }
+
+    print!("This will {} {} tasks, continue? [y/N] ", action, count);
+    io::stdout().flush().expect("Could not flush stdout");
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .expect("Could not read answer");
+
+    if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        eprintln!("Aborted");
+        exit(1);
+    }
+}
+
+// Run an action on every selected task, keeping the cache up to date and reporting each result
+fn apply_tasks<F>(
+    taiga: &mut Taiga,
+    tasks: &mut TaigaTasks,
+    selected: Vec<TaigaTask>,
+    action: &str,
+    apply: F,
+) where
+    F: FnMut(&mut Taiga, &TaigaTask) -> Result<TaigaTask>,
+{
+    apply_cached_tasks(taiga, tasks, selected, action, false, apply);
+}
+
+// Apply a change to each selected task, then update the cache, dropping the changed
+// tasks from it when they do not exist anymore
+fn apply_cached_tasks<F>(
+    taiga: &mut Taiga,
+    tasks: &mut TaigaTasks,
+    selected: Vec<TaigaTask>,
+    action: &str,
+    remove: bool,
+    mut apply: F,
+) where
+    F: FnMut(&mut Taiga, &TaigaTask) -> Result<TaigaTask>,
+{
+    let single = selected.len() == 1;
+    let mut failed = 0;
+
+    for task in &selected {
+        match apply(taiga, task) {
+            Ok(new_task) => {
+                if !single {
+                    println!("{}: {}", action, new_task.name);
+                }
+                if remove {
+                    tasks.tasks.retain(|t| t.id != new_task.id);
+                } else if let Some(cached) = tasks.tasks.iter_mut().find(|t| t.id == new_task.id) {
+                    *cached = new_task;
+                }
+            }
+            Err(err) => {
+                if single {
+                    eprintln!("Error, could not {} task: {}", action, err);
+                    exit(1);
+                }
+                eprintln!("Error, could not {} {}: {}", action, task.name, err);
+                failed += 1;
+            }
+        }
+    }
+
+    tasks.clone().save_cache();
+
+    if !single {
+        println!("{} succeeded, {} failed", selected.len() - failed, failed);
+        if failed > 0 {
+            exit(1);
+        }
+    }
+}
+
+// Warn about or refuse a move that takes a status over its WIP limit, counting every story
+// of the project since the cached ones are only those of the last search
+fn check_wip_limit(
+    taiga: &mut Taiga,
+    config: &TaigaConfig,
+    tasks: &TaigaTasks,
+    status_id: i32,
+    selected: &[TaigaTask],
+) {
+    let status = match tasks.statuses.iter().find(|s| s.id == status_id) {
+        Some(status) => status,
+        None => return,
+    };
+    // the project is only fetched when the move could go over a limit
+    let (incoming, limit) = match wip_incoming(status, selected) {
+        Some(load) => load,
+        None => return,
+    };
+
+    let total = match taiga.get_tasks(tasks.id) {
+        Ok(project_tasks) => match wip_overflow(status_id, &project_tasks, incoming, limit) {
+            Some(total) => total,
+            None => return,
+        },
+        Err(err) => {
+            eprintln!("Warning, could not check the WIP limit: {}", err);
+            return;
+        }
+    };
+
+    let message = format!(
+        "{} would hold {} stories, over its WIP limit of {}",
+        status.slug, total, limit
+    );
+    match config.wip_limits {
+        WipPolicy::Warn => eprintln!("Warning, {}", message),
+        WipPolicy::Refuse => {
+            eprintln!("Error, {}", message);
+            exit(1);
+        }
+    }
+}
+
+// The number of selected tasks that would enter a status with a WIP limit, and that limit
+fn wip_incoming(status: &TaigaStatus, selected: &[TaigaTask]) -> Option<(i32, i32)> {
+    let limit = status.wip_limit.filter(|limit| *limit > 0)?;
+
+    let incoming = selected
+        .iter()
+        .filter(|task| task.status_id != status.id)
+        .count() as i32;
+
+    (incoming > 0).then_some((incoming, limit))
+}
+
+// The number of tasks a status would hold after the move, when that is over its limit
+fn wip_overflow(
+    status_id: i32,
+    project_tasks: &[TaigaTask],
+    incoming: i32,
+    limit: i32,
+) -> Option<i32> {
+    let current = project_tasks
+        .iter()
+        .filter(|task| task.status_id == status_id)
+        .count() as i32;
+
+    (current + incoming > limit).then_some(current + incoming)
+}
+
+// Move the selected tasks to a status, in a single request when there are several of them
+fn move_tasks(
+    taiga: &mut Taiga,
+    tasks: &mut TaigaTasks,
+    selected: Vec<TaigaTask>,
+    status_id: i32,
+    action: &str,
+) {
+    // hooks see every task on its own, so they rule out the bulk request
+    if selected.len() > 1 && !has_hooks(action) {
+        let task_ids = selected.iter().map(|task| task.id).collect();
+        if let Ok(moved) = taiga.bulk_move_tasks(tasks.id, status_id, task_ids) {
+            if let Ok(project_tasks) = taiga.get_tasks(tasks.id) {
+                for task in project_tasks {
+                    if let Some(cached) = tasks.tasks.iter_mut().find(|t| t.id == task.id) {
+                        *cached = task;
+                    }
+                }
+            }
+            tasks.clone().save_cache();
+
+            let mut failed = 0;
+            for task in &selected {
+                if moved.contains(&task.id) {
+                    println!("{}: {}", action, task.name);
+                } else {
+                    eprintln!("Error, could not {} {}", action, task.name);
+                    failed += 1;
+                }
+            }
+            println!("{} succeeded, {} failed", selected.len() - failed, failed);
+            if failed > 0 {
+                exit(1);
+            }
+            return;
+        }
+    }
+
+    let status = tasks
+        .statuses
+        .iter()
+        .find(|s| s.id == status_id)
+        .map(|s| s.slug.clone())
+        .unwrap_or_default();
+
+    apply_tasks(taiga, tasks, selected, action, |taiga, task| {
+        let new = TaigaTask {
+            status_id,
+            status: status.clone(),
+            ..task.clone()
+        };
+        hooked_update(taiga, action, task, new)
+    });
+}
+
+// Run a change through the pre hooks of the command, push it and run the post hooks
+fn hooked_update(
+    taiga: &mut Taiga,
+    command: &str,
+    old: &TaigaTask,
+    new: TaigaTask,
+) -> Result<TaigaTask> {
+    let new = run_pre_hooks(command, Some(old), Some(new.clone()))?.unwrap_or(new);
+    let task = update_task(taiga, old, &new)?;
+    run_post_hooks(command, Some(old), Some(&task));
+    Ok(task)
+}
+
+// Push the differences between two versions of a task, as a plain move when only the status changed
+fn update_task(taiga: &mut Taiga, old: &TaigaTask, new: &TaigaTask) -> Result<TaigaTask> {
+    let status_only = new.name == old.name
+        && new.assigned == old.assigned
+        && new.due == old.due
+        && new.team == old.team
+        && new.client == old.client
+        && new.blocked == old.blocked;
+
+    let mut task = if status_only {
+        taiga.move_task(old.id, new.status_id, old.version)?
+    } else {
+        taiga.modify_task(
+            old.id,
+            new.status_id,
+            new.name.clone(),
+            new.assigned.clone(),
+            new.due.map(|due| due.format("%Y-%m-%d").to_string()),
+            new.team,
+            new.client,
+            new.blocked,
+            old.version,
+        )?
+    };
+
+    if new.tags != old.tags {
+        task = taiga.detail_task(
+            task.id,
+            None,
+            Some(new.tags.clone()),
+            HashMap::new(),
+            task.version,
+        )?;
+    }
+
+    Ok(task)
+}
+
+// Resolve a user given on the command line, `me` being the logged in user
+fn resolve_member(user_id: i32, members: &[TaigaUser], name: &str) -> i32 {
+    if name == "me" {
+        if members.iter().any(|member| member.id == user_id) {
+            return user_id;
+        }
+        eprintln!("Could not find your username on the project");
+        exit(1);
+    }
+
+    find_member(members, name)
+        .map(|member| member.id)
+        .unwrap_or_else(|err| {
+            eprintln!("Error, {}", err);
+            exit(1);
+        })
+}
+
+// Whether the cached members may be missing a user given on the command line
+fn member_outdated(members: &[TaigaUser], name: &str) -> bool {
+    name != "me" && find_member(members, name).is_err()
+}
+
+// Whether the cached statuses or members are missing something the filter refers to
+fn filter_outdated(tasks: &TaigaTasks, filter: &TaskFilter) -> bool {
+    for status in &filter.include_statuses {
+        if !tasks.statuses.iter().any(|s| s.slug == *status) {
+            return true;
+        }
+    }
+    for status in &filter.exclude_statuses {
+        if !tasks.statuses.iter().any(|s| s.slug == *status) {
+            return true;
+        }
+    }
+    for username in &filter.include_assigned {
+        if member_outdated(&tasks.members, username) {
+            return true;
+        }
+    }
+    for username in &filter.exclude_assigned {
+        if member_outdated(&tasks.members, username) {
+            return true;
+        }
+    }
+    for username in &filter.watching {
+        if member_outdated(&tasks.members, username) {
+            return true;
+        }
+    }
+    for (name, _) in &filter.attributes {
+        if find_attribute(&tasks.attributes, name).is_none() {
+            return true;
+        }
     }
+    false
 }
 
-pub fn taiga_team(taiga: &mut Taiga, args: TeamTaskArgs) {
-    // getting necessary information
-    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
-        eprintln!("{}", err);
-        exit(1);
-    });
-    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
-    let task = tasks.get_task(args.id);
-
-    // pushing the change
-    if let Ok(new_task) = taiga.team_task(task.id, args.remove, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not assign task");
-        exit(1);
+// Filter the tasks, loading the custom attribute values of the candidates when the filter
+// refers to them since they are not part of the story list
+fn filter_with_attributes(
+    taiga: &mut Taiga,
+    tasks: &TaigaTasks,
+    filter: &TaskFilter,
+) -> Vec<TaigaTask> {
+    if filter.attributes.is_empty() {
+        return filter_tasks(taiga.id, tasks, filter);
     }
-}
 
-pub fn taiga_client(taiga: &mut Taiga, args: ClientTaskArgs) {
-    // getting necessary information
-    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
-        eprintln!("{}", err);
+    let mut candidates = tasks.clone();
+    candidates.tasks = filter_tasks(
+        taiga.id,
+        tasks,
+        &TaskFilter {
+            attributes: Vec::new(),
+            ..filter.clone()
+        },
+    );
+    load_attributes(taiga, &mut candidates.tasks).unwrap_or_else(|err| {
+        eprintln!("Error, could not get attributes: {}", err);
         exit(1);
     });
-    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
-    let task = tasks.get_task(args.id);
 
-    // pushing the change
-    if let Ok(new_task) = taiga.client_task(task.id, args.remove, task.version) {
-        *task = new_task;
-        tasks.save_cache();
-    } else {
-        eprintln!("Error, could not assign task");
-        exit(1);
+    filter_tasks(taiga.id, &candidates, filter)
+}
+
+// Load the custom attribute values of the tasks that have none cached yet
+fn load_attributes(taiga: &mut Taiga, tasks: &mut [TaigaTask]) -> Result<()> {
+    for task in tasks.iter_mut().filter(|task| task.attributes.is_empty()) {
+        task.attributes = taiga.get_attribute_values(task.id)?;
     }
+    Ok(())
 }
 
-pub fn taiga_block(taiga: &mut Taiga, args: BlockTaskArgs) {
-    // getting necessary information
-    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
-        eprintln!("{}", err);
-        exit(1);
-    });
-    let mut tasks = taiga.tasks_from_cache(project.id, |_| false);
-    let task = tasks.get_task(args.id);
+// Carry the attribute values over from the cached tasks whose story did not change since
+fn keep_attributes(tasks: &mut [TaigaTask], cached: &[TaigaTask]) {
+    for task in tasks {
+        if let Some(old) = cached
+            .iter()
+            .find(|old| old.id == task.id && old.version == task.version)
+        {
+            task.attributes = old.attributes.clone();
+        }
+    }
+}
 
-    // pushing the change
-    if let Ok(new_task) = taiga.block_task(task.id, args.remove, task.version) {
-        *task = new_task;
-        tasks.save_cache();
+// The stories a filter searches, those of archived statuses only when asked for
+fn fetch_tasks(taiga: &mut Taiga, id: i32, filter: &TaskFilter) -> Result<Vec<TaigaTask>> {
+    if filter.archived {
+        taiga.get_all_tasks(id)
     } else {
-        eprintln!("Error, could not assign task");
-        exit(1);
+        taiga.get_tasks(id)
     }
 }
 
-pub fn taiga_modify(taiga: &mut Taiga, args: ModifyTaskArgs) {
-    let project = taiga.find_project(args.project).unwrap_or_else(|err| {
-        eprintln!("{}", err);
-        exit(1);
-    });
-    let mut tasks = taiga.tasks_from_cache(project.id, |tasks| {
-        if let Some(status) = &args.status {
-            if !tasks.statuses.iter().any(|s| s.slug == *status) {
-                return true;
-            }
-        }
-        if let Some(assign) = &args.assign {
-            for username in assign {
-                if username != "me" && !tasks.members.iter().any(|m| m.username == *username) {
-                    return true;
-                }
-            }
-        }
-        false
-    });
+// Keep the tasks matching every modifier of the filter, closed ones only when asked for
+// or when the filter names a closed status
+fn filter_tasks(user_id: i32, tasks: &TaigaTasks, filter: &TaskFilter) -> Vec<TaigaTask> {
+    let mut include_status_ids = Vec::new();
+    for status in &filter.include_statuses {
+        let status_id = if let Some(status) = tasks.statuses.iter().find(|s| s.slug == *status) {
+            status.id
+        } else {
+            eprintln!("Error, could not find given status");
+            exit(1);
+        };
+        include_status_ids.push(status_id);
+    }
 
-    let mut status_id = 0;
-    if let Some(status) = &args.status {
-        status_id = if let Some(status) = tasks.statuses.iter().find(|s| s.slug == *status) {
+    let mut exclude_status_ids = Vec::new();
+    for status in &filter.exclude_statuses {
+        let status_id = if let Some(status) = tasks.statuses.iter().find(|s| s.slug == *status) {
             status.id
         } else {
             eprintln!("Error, could not find given status");
             exit(1);
         };
+        exclude_status_ids.push(status_id);
     }
 
-    let mut assigned_ids = Vec::new();
-    if let Some(assign) = &args.assign {
-        for username in assign {
-            let member_id = if username == "me" {
-                tasks
-                    .members
-                    .iter()
-                    .find(|member| member.id == taiga.id)
-                    .map(|m| m.id)
-                    .unwrap_or_else(|| {
-                        eprintln!("Could not find your username on the project");
-                        exit(1);
-                    })
-            } else {
-                tasks
-                    .members
-                    .iter()
-                    .find(|member| member.username == *username)
-                    .map(|m| m.id)
-                    .unwrap_or_else(|| {
-                        eprintln!("Could not find username on the project");
-                        exit(1);
-                    })
-            };
-            assigned_ids.push(member_id);
-        }
+    let mut include_member_ids = Vec::new();
+    for username in &filter.include_assigned {
+        let member_id = resolve_member(user_id, &tasks.members, username);
+        include_member_ids.push(member_id);
+    }
+    let mut exclude_member_ids = Vec::new();
+    for username in &filter.exclude_assigned {
+        let member_id = resolve_member(user_id, &tasks.members, username);
+        exclude_member_ids.push(member_id);
+    }
+    let mut watcher_ids = Vec::new();
+    for username in &filter.watching {
+        // watching does not need you to be a member
+        let member_id = if username == "me" {
+            user_id
+        } else {
+            resolve_member(user_id, &tasks.members, username)
+        };
+        watcher_ids.push(member_id);
     }
 
-    let task = tasks.get_task(args.id);
+    let mut attributes = Vec::new();
+    for (name, value) in &filter.attributes {
+        let attribute = find_attribute(&tasks.attributes, name).unwrap_or_else(|| {
+            eprintln!("Error, could not find given attribute");
+            exit(1);
+        });
+        attributes.push((attribute, value));
+    }
 
-    let status = if args.status.is_some() {
-        status_id
-    } else {
-        task.status_id
-    };
+    let include_closed = filter.closed
+        || tasks
+            .statuses
+            .iter()
+            .any(|status| status.is_closed && include_status_ids.contains(&status.id));
 
-    let rename = if let Some(rename) = args.rename {
-        rename
-    } else {
-        task.name.clone()
-    };
+    tasks
+        .tasks
+        .iter()
+        .filter(|task| {
+            if task.closed && !include_closed {
+                return false;
+            }
 
-    let assign = if args.assign.is_some() {
-        let mut combined_ids = task.assigned.clone();
-        combined_ids.extend(assigned_ids);
-        combined_ids.sort();
-        combined_ids.dedup();
-        combined_ids
-    } else {
-        task.assigned.clone()
-    };
+            if let Some(team) = filter.team {
+                if task.team != team {
+                    return false;
+                }
+            }
 
-    let due_date = args
-        .due_date
-        .or_else(|| task.due.map(|due| due.format("%Y-%m-%d").to_string()));
+            if let Some(client) = filter.client {
+                if task.client != client {
+                    return false;
+                }
+            }
 
-    let team = if let Some(team) = args.team {
-        team
-    } else {
-        task.team
-    };
+            if let Some(block) = filter.block {
+                if task.blocked != block {
+                    return false;
+                }
+            }
 
-    let client = if let Some(client) = args.client {
-        client
-    } else {
-        task.client
-    };
+            if let Some(due_date) = &filter.due_date {
+                if due_date.is_empty() {
+                    if task.due.is_some() {
+                        return false;
+                    }
+                } else if let Some(task_due) = task.due {
+                    let a = task_due.date_naive();
+                    let b = NaiveDate::parse_from_str(due_date, "%Y-%m-%d")
+                        .expect("Could not parse due date");
+                    if a > b {
+                        return false;
+                    }
+                } else {
+                    return false;
+                }
+            }
 
-    let block = if let Some(block) = args.block {
-        block
-    } else {
-        task.blocked
-    };
+            if !include_member_ids.is_empty()
+                && !task
+                    .assigned
+                    .iter()
+                    .any(|id| include_member_ids.iter().any(|member_id| member_id == id))
+            {
+                return false;
+            }
 
-    if let Ok(new_task) = taiga.modify_task(
-        task.id,
-        status,
-        rename,
-        assign,
-        due_date,
-        team,
-        client,
-        block,
-        task.version,
-    ) {
-        *task = new_task;
-        tasks.save_cache();
+            if !exclude_member_ids.is_empty()
+                && task
+                    .assigned
+                    .iter()
+                    .any(|id| exclude_member_ids.iter().any(|member_id| member_id == id))
+            {
+                return false;
+            }
+
+            if !watcher_ids.is_empty() && !watcher_ids.iter().any(|id| task.watchers.contains(id)) {
+                return false;
+            }
+
+            if !include_status_ids.is_empty() && !include_status_ids.contains(&task.status_id) {
+                return false;
+            }
+
+            if !exclude_status_ids.is_empty() && exclude_status_ids.contains(&task.status_id) {
+                return false;
+            }
+
+            if !attributes.iter().all(|(attribute, value)| {
+                attribute.matches(task.attributes.get(&attribute.id), value)
+            }) {
+                return false;
+            }
+
+            if !fzf_match(&task.name, &filter.query) {
+                return false;
+            }
+
+            true
+        })
+        .cloned()
+        .collect()
+}
+
+fn format_size(size: i64) -> String {
+    let units = ["B", "KB", "MB", "GB"];
+    let mut size = size as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", size, units[unit])
     } else {
-        eprintln!("Error, could not assign task");
-        exit(1);
+        format!("{:.1} {}", size, units[unit])
     }
 }
 
@@ -899,3 +3652,383 @@ fn fzf_match(input: &str, query: &[String]) -> bool {
 
     index_match == query.len()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: i32, status_id: i32) -> TaigaTask {
+        TaigaTask {
+            id,
+            reference: id,
+            name: format!("Story {}", id),
+            status_id,
+            status: String::new(),
+            team: false,
+            client: false,
+            blocked: false,
+            assigned: Vec::new(),
+            due: None,
+            swimlane: None,
+            closed: false,
+            tags: Vec::new(),
+            watchers: Vec::new(),
+            votes: 0,
+            version: 1,
+            attributes: HashMap::new(),
+        }
+    }
+
+    fn status(id: i32, slug: &str, is_closed: bool) -> TaigaStatus {
+        TaigaStatus {
+            id,
+            slug: slug.to_string(),
+            is_closed,
+            color: String::new(),
+            order: id,
+            wip_limit: None,
+            is_archived: false,
+        }
+    }
+
+    fn member(id: i32, username: &str) -> TaigaUser {
+        TaigaUser {
+            id,
+            username: username.to_string(),
+            full_name: String::new(),
+            role: String::new(),
+            email: None,
+            is_active: true,
+        }
+    }
+
+    fn project_tasks(tasks: Vec<TaigaTask>) -> TaigaTasks {
+        TaigaTasks {
+            id: 1,
+            tasks,
+            members: vec![member(7, "alice"), member(8, "bob")],
+            statuses: vec![status(1, "new", false), status(2, "done", true)],
+            attributes: Vec::new(),
+        }
+    }
+
+    fn ids(tasks: &[TaigaTask]) -> Vec<i32> {
+        tasks.iter().map(|task| task.id).collect()
+    }
+
+    #[test]
+    fn highlights_moved_reassigned_and_new_tasks() {
+        let previous: HashMap<i32, TaigaTask> = (1..=4).map(|id| (id, task(id, 1))).collect();
+
+        let mut moved = task(1, 2);
+        moved.version = 2;
+        let mut renamed = task(2, 1);
+        renamed.name = "Renamed".to_string();
+        renamed.version = 2;
+        let mut reassigned = task(3, 1);
+        reassigned.assigned = vec![7];
+        reassigned.version = 2;
+        let tasks = vec![moved, renamed, reassigned, task(4, 1), task(5, 1)];
+
+        let mut changed: Vec<i32> = changed_tasks(&previous, &tasks).into_iter().collect();
+        changed.sort();
+        assert_eq!(changed, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn filters_on_watchers() {
+        let mut watched = task(1, 1);
+        watched.watchers = vec![3, 7];
+        let tasks = project_tasks(vec![watched, task(2, 1)]);
+
+        // you can watch the stories of a project you are not a member of
+        let filter = TaskFilter {
+            watching: vec!["me".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter_tasks(3, &tasks, &filter)), vec![1]);
+
+        let filter = TaskFilter {
+            watching: vec!["bob".to_string()],
+            ..Default::default()
+        };
+        assert!(filter_tasks(3, &tasks, &filter).is_empty());
+    }
+
+    #[test]
+    fn groups_the_board_by_swimlane() {
+        let lanes = vec![
+            TaigaSwimlane {
+                id: 10,
+                name: "Urgent".to_string(),
+                order: 1,
+            },
+            TaigaSwimlane {
+                id: 11,
+                name: "Later".to_string(),
+                order: 2,
+            },
+        ];
+        let mut urgent = task(1, 1);
+        urgent.swimlane = Some(10);
+        let mut removed_lane = task(2, 1);
+        removed_lane.swimlane = Some(99);
+        let tasks = vec![urgent, removed_lane, task(3, 1)];
+
+        let grouped = board_lanes(&lanes, &tasks);
+        let grouped: Vec<(&str, Vec<i32>)> = grouped
+            .iter()
+            .map(|(name, tasks)| (name.as_str(), tasks.iter().map(|task| task.id).collect()))
+            .collect();
+        assert_eq!(
+            grouped,
+            vec![
+                ("Urgent", vec![1]),
+                ("Later", vec![]),
+                ("Unclassified", vec![2, 3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn boards_without_swimlanes_have_a_single_lane() {
+        let tasks = vec![task(1, 1), task(2, 2)];
+
+        let grouped = board_lanes(&[], &tasks);
+        assert_eq!(grouped.len(), 1);
+        assert_eq!(grouped[0].1.len(), 2);
+
+        let grouped = board_lanes(&[], &[]);
+        assert_eq!(grouped.len(), 1);
+        assert!(grouped[0].1.is_empty());
+    }
+
+    #[test]
+    fn counts_only_tasks_entering_a_limited_status() {
+        let mut doing = status(3, "doing", false);
+        assert_eq!(wip_incoming(&doing, &[task(1, 1)]), None);
+
+        doing.wip_limit = Some(0);
+        assert_eq!(wip_incoming(&doing, &[task(1, 1)]), None);
+
+        doing.wip_limit = Some(2);
+        assert_eq!(
+            wip_incoming(&doing, &[task(1, 1), task(2, 3)]),
+            Some((1, 2))
+        );
+        assert_eq!(wip_incoming(&doing, &[task(2, 3)]), None);
+    }
+
+    #[test]
+    fn detects_moves_over_the_wip_limit() {
+        let project = vec![task(1, 3), task(2, 3), task(3, 1)];
+
+        assert_eq!(wip_overflow(3, &project, 1, 3), None);
+        assert_eq!(wip_overflow(3, &project, 2, 3), Some(4));
+        assert_eq!(wip_overflow(1, &project, 2, 2), Some(3));
+    }
+
+    #[test]
+    fn hides_closed_tasks_unless_asked_for() {
+        let mut closed = task(2, 2);
+        closed.closed = true;
+        let tasks = project_tasks(vec![task(1, 1), closed]);
+
+        assert_eq!(
+            ids(&filter_tasks(3, &tasks, &TaskFilter::default())),
+            vec![1]
+        );
+
+        let filter = TaskFilter {
+            closed: true,
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter_tasks(3, &tasks, &filter)), vec![1, 2]);
+
+        // naming a closed status is asking for its tasks
+        let filter = TaskFilter {
+            include_statuses: vec!["done".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter_tasks(3, &tasks, &filter)), vec![2]);
+    }
+
+    #[test]
+    fn reopens_to_the_first_open_status() {
+        let mut archived = status(1, "icebox", false);
+        archived.is_archived = true;
+        let statuses = vec![
+            status(4, "done", true),
+            archived,
+            status(2, "new", false),
+            status(3, "doing", false),
+        ];
+        assert_eq!(first_open_status(&statuses), Some(2));
+
+        assert_eq!(first_open_status(&[status(4, "done", true)]), None);
+    }
+
+    #[test]
+    fn reorders_statuses_without_repeats() {
+        let statuses = vec![
+            status(1, "new", false),
+            status(2, "doing", false),
+            status(3, "review", false),
+            status(4, "done", true),
+        ];
+        assert_eq!(status_order(&[3, 1], &statuses), vec![3, 1, 2, 4]);
+
+        // a status given twice keeps its first place
+        assert_eq!(status_order(&[4, 2, 4], &statuses), vec![4, 2, 1, 3]);
+    }
+
+    #[test]
+    fn counts_open_and_overdue_stories() {
+        let today = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+        let due = |day: u32| {
+            Some(
+                NaiveDate::from_ymd_opt(2024, 5, day)
+                    .unwrap()
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap()
+                    .and_utc(),
+            )
+        };
+
+        let mut late = task(1, 1);
+        late.assigned = vec![7, 8];
+        late.due = due(9);
+        let mut due_today = task(2, 1);
+        due_today.assigned = vec![7];
+        due_today.due = due(10);
+        let mut undated = task(3, 1);
+        undated.assigned = vec![7];
+        let tasks = vec![late, due_today, undated, task(4, 1)];
+
+        assert_eq!(workload(7, &tasks, today), (3, 1));
+        assert_eq!(workload(8, &tasks, today), (1, 1));
+        assert_eq!(workload(9, &tasks, today), (0, 0));
+    }
+
+    #[test]
+    fn groups_the_timesheet_by_project_id() {
+        let entry = |project_id: i32, project: &str, reference: i32, minutes: i64| TaigaTimeEntry {
+            project_id,
+            project: project.to_string(),
+            task_id: project_id * 100 + reference,
+            reference,
+            name: format!("Story {}", reference),
+            started: Utc::now(),
+            minutes,
+            note: None,
+        };
+        let entries = [
+            entry(1, "Website", 3, 30),
+            entry(2, "Website", 3, 15),
+            entry(1, "Website", 3, 45),
+            entry(1, "Website", 5, 10),
+        ];
+        let entries: Vec<&TaigaTimeEntry> = entries.iter().collect();
+
+        let projects = timesheet(&entries);
+        let totals: Vec<(i32, Vec<(i32, i64)>)> = projects
+            .iter()
+            .map(|(id, stories)| {
+                (
+                    *id,
+                    stories
+                        .iter()
+                        .map(|(reference, (_, minutes))| (*reference, *minutes))
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            totals,
+            vec![(1, vec![(3, 75), (5, 10)]), (2, vec![(3, 15)])]
+        );
+    }
+
+    #[test]
+    fn keeps_the_attributes_of_unchanged_stories() {
+        let mut cached = vec![task(1, 1), task(2, 1)];
+        cached[0].attributes = HashMap::from([(3, "Backend".to_string())]);
+        cached[1].attributes = HashMap::from([(3, "Frontend".to_string())]);
+
+        let mut changed = task(2, 1);
+        changed.version = 2;
+        let mut tasks = vec![task(1, 1), changed, task(4, 1)];
+        keep_attributes(&mut tasks, &cached);
+
+        assert_eq!(tasks[0].attributes[&3], "Backend");
+        assert!(tasks[1].attributes.is_empty());
+        assert!(tasks[2].attributes.is_empty());
+    }
+
+    #[test]
+    fn loads_no_attributes_already_kept() {
+        // nothing listens there, so any request would fail
+        let mut taiga = Taiga {
+            auth_token: String::new(),
+            refresh: String::new(),
+            refresh_time: std::time::SystemTime::now(),
+            url: "http://127.0.0.1:1".to_string(),
+            id: 3,
+            username: String::new(),
+            password: String::new(),
+            projects: Vec::new(),
+        };
+        let mut tasks = vec![task(1, 1)];
+        tasks[0].attributes = HashMap::from([(3, "Backend".to_string())]);
+
+        assert!(load_attributes(&mut taiga, &mut tasks).is_ok());
+        assert!(load_attributes(&mut taiga, &mut [task(2, 1)]).is_err());
+    }
+
+    #[test]
+    fn filters_on_custom_attributes() {
+        let mut backend = task(1, 1);
+        backend.attributes = HashMap::from([(3, "Backend".to_string())]);
+        let mut tasks = project_tasks(vec![backend, task(2, 1)]);
+        tasks.attributes = vec![TaigaAttribute {
+            id: 3,
+            name: "Component".to_string(),
+            kind: "text".to_string(),
+        }];
+
+        let filter = |value: &str| TaskFilter {
+            attributes: vec![("component".to_string(), value.to_string())],
+            ..Default::default()
+        };
+        assert_eq!(ids(&filter_tasks(3, &tasks, &filter("backend"))), vec![1]);
+        assert_eq!(ids(&filter_tasks(3, &tasks, &filter(""))), vec![2]);
+    }
+
+    #[test]
+    fn merges_assignee_changes() {
+        // assign:=a,b
+        assert_eq!(
+            merge_assignees(&[7, 8], Some(&[9, 4]), &[], &[]),
+            vec![9, 4]
+        );
+        // assign:+c
+        assert_eq!(merge_assignees(&[7, 8], None, &[9], &[]), vec![7, 8, 9]);
+        // assign:-a
+        assert_eq!(merge_assignees(&[7, 8], None, &[], &[7]), vec![8]);
+        // assign: alone
+        assert_eq!(
+            merge_assignees(&[7, 8], Some(&[]), &[], &[]),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn keeps_the_main_assignee_and_the_given_order() {
+        // adding someone already assigned does not move them
+        assert_eq!(merge_assignees(&[7, 8], None, &[8, 9], &[]), vec![7, 8, 9]);
+        assert_eq!(
+            merge_assignees(&[7], Some(&[9, 4, 9]), &[7], &[4]),
+            vec![9, 7]
+        );
+    }
+}
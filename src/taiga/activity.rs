@@ -0,0 +1,203 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::Taiga;
+
+// What the user had seen of each project the last time the digest was shown
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TaigaActivity {
+    pub projects: HashMap<i32, TaigaProjectActivity>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaProjectActivity {
+    pub last_seen: DateTime<Utc>,
+    pub assigned: Vec<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TaigaEventKind {
+    Moved(String, String),
+    Commented,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaigaEvent {
+    pub user_id: Option<i32>,
+    pub reference: i32,
+    pub name: String,
+    pub username: String,
+    pub kind: TaigaEventKind,
+    pub created: DateTime<Utc>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TimelineResponse {
+    event_type: String,
+    created: DateTime<Utc>,
+    data: TimelineDataResponse,
+}
+
+#[derive(Deserialize, Debug)]
+struct TimelineDataResponse {
+    user: Option<TimelineUserResponse>,
+    userstory: Option<TimelineStoryResponse>,
+    values_diff: Option<HashMap<String, Value>>,
+    comment: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TimelineUserResponse {
+    id: Option<i32>,
+    #[serde(default)]
+    username: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct TimelineStoryResponse {
+    #[serde(rename = "ref")]
+    reference: i32,
+    subject: String,
+}
+
+impl Taiga {
+    // Story moves and comments of a project since the given date, oldest first
+    pub fn get_events(&mut self, project_id: i32, since: DateTime<Utc>) -> Result<Vec<TaigaEvent>> {
+        let timeline =
+            self.get::<Vec<TimelineResponse>>(&format!("/timeline/project/{}", project_id))?;
+
+        Ok(timeline_events(timeline, since))
+    }
+}
+
+// The story moves and comments of a timeline after the given date, oldest first
+fn timeline_events(timeline: Vec<TimelineResponse>, since: DateTime<Utc>) -> Vec<TaigaEvent> {
+    let mut events: Vec<TaigaEvent> = timeline
+        .into_iter()
+        .filter(|entry| entry.created > since && entry.event_type == "userstories.userstory.change")
+        .filter_map(|entry| {
+            let story = entry.data.userstory?;
+            let (user_id, username) = match entry.data.user {
+                Some(user) => (user.id, user.username),
+                None => (None, String::new()),
+            };
+
+            let moved = entry
+                .data
+                .values_diff
+                .as_ref()
+                .and_then(|diff| diff.get("status"))
+                .and_then(|status| status.as_array())
+                .and_then(|status| match status.as_slice() {
+                    [from, to] => Some((value_name(from), value_name(to))),
+                    _ => None,
+                });
+
+            let kind = match moved {
+                Some((from, to)) => TaigaEventKind::Moved(from, to),
+                None if entry.data.comment.is_some_and(|c| !c.is_empty()) => {
+                    TaigaEventKind::Commented
+                }
+                None => return None,
+            };
+
+            Some(TaigaEvent {
+                user_id,
+                reference: story.reference,
+                name: story.subject,
+                username,
+                kind,
+                created: entry.created,
+            })
+        })
+        .collect();
+
+    events.sort_by_key(|event| event.created);
+    events
+}
+
+fn value_name(value: &Value) -> String {
+    match value {
+        Value::String(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(created: &str, data: Value) -> Value {
+        json!({
+            "event_type": "userstories.userstory.change",
+            "created": created,
+            "data": data
+        })
+    }
+
+    fn story() -> Value {
+        json!({ "ref": 12, "subject": "Fix login" })
+    }
+
+    #[test]
+    fn reads_moves_and_comments_in_order() {
+        let timeline: Vec<TimelineResponse> = serde_json::from_value(json!([
+            entry(
+                "2026-10-02T10:00:00Z",
+                json!({
+                    "user": { "id": 7, "username": "alice" },
+                    "userstory": story(),
+                    "comment": "On staging"
+                })
+            ),
+            entry(
+                "2026-10-02T09:00:00Z",
+                json!({
+                    "user": { "id": 8, "username": "bob" },
+                    "userstory": story(),
+                    "values_diff": { "status": ["New", "Done"] }
+                })
+            ),
+        ]))
+        .unwrap();
+        let since = "2026-10-01T00:00:00Z".parse().unwrap();
+
+        let events = timeline_events(timeline, since);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0].kind,
+            TaigaEventKind::Moved(from, to) if from == "New" && to == "Done"
+        ));
+        assert_eq!(events[0].username, "bob");
+        assert!(matches!(events[1].kind, TaigaEventKind::Commented));
+        assert_eq!((events[1].reference, events[1].user_id), (12, Some(7)));
+    }
+
+    #[test]
+    fn skips_old_and_unrelated_entries() {
+        let timeline: Vec<TimelineResponse> = serde_json::from_value(json!([
+            entry(
+                "2026-09-30T10:00:00Z",
+                json!({ "userstory": story(), "comment": "Before" })
+            ),
+            entry(
+                "2026-10-02T10:00:00Z",
+                json!({ "userstory": story(), "values_diff": { "subject": ["A", "B"] } })
+            ),
+            entry("2026-10-02T10:00:00Z", json!({ "userstory": story(), "comment": "" })),
+            {
+                "event_type": "tasks.task.change",
+                "created": "2026-10-02T10:00:00Z",
+                "data": { "userstory": story(), "comment": "Task" }
+            },
+        ]))
+        .unwrap();
+        let since = "2026-10-01T00:00:00Z".parse().unwrap();
+
+        assert!(timeline_events(timeline, since).is_empty());
+    }
+}
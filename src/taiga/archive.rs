@@ -0,0 +1,821 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{find_member, Taiga, TaigaUser};
+
+// Bumped whenever the archive layout changes in a way older versions cannot restore
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectArchive {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: ArchiveProject,
+    pub statuses: Vec<ArchiveStatus>,
+    pub points: Vec<ArchivePoint>,
+    pub members: Vec<ArchiveMember>,
+    pub milestones: Vec<ArchiveMilestone>,
+    pub epics: Vec<ArchiveEpic>,
+    pub stories: Vec<ArchiveStory>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveProject {
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveStatus {
+    pub name: String,
+    pub slug: String,
+    pub color: String,
+    pub order: i32,
+    pub is_closed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivePoint {
+    pub name: String,
+    pub value: Option<f64>,
+    pub order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveMember {
+    pub username: String,
+    pub full_name: String,
+    pub role: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveMilestone {
+    pub name: String,
+    pub estimated_start: String,
+    pub estimated_finish: String,
+    pub closed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveEpic {
+    pub reference: i32,
+    pub subject: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub stories: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveStory {
+    pub reference: i32,
+    pub subject: String,
+    pub description: String,
+    pub status: String,
+    pub tags: Vec<String>,
+    pub assigned: Vec<String>,
+    pub due_date: Option<String>,
+    pub milestone: Option<String>,
+    pub points: HashMap<String, Option<f64>>,
+    pub team: bool,
+    pub client: bool,
+    pub blocked: bool,
+    pub comments: Vec<ArchiveComment>,
+    pub attachments: Vec<ArchiveAttachment>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveComment {
+    pub author: String,
+    pub created_at: String,
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchiveAttachment {
+    pub name: String,
+    pub size: i64,
+    pub url: String,
+    pub description: String,
+    pub created_date: String,
+}
+
+// Counts of what a restore recreated, and of what it had to leave out
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    pub statuses: usize,
+    pub milestones: usize,
+    pub stories: usize,
+    pub comments: usize,
+    pub epics: usize,
+    pub missing_members: Vec<String>,
+    pub skipped_attachments: usize,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArchiveProjectResponse {
+    name: String,
+    slug: String,
+    #[serde(default)]
+    description: String,
+    members: Vec<ArchiveMemberResponse>,
+    us_statuses: Vec<ArchiveStatusResponse>,
+    points: Vec<ArchivePointResponse>,
+    roles: Vec<ArchiveRoleResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArchiveMemberResponse {
+    id: i32,
+    username: String,
+    #[serde(default)]
+    full_name: String,
+    #[serde(default)]
+    role_name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArchiveStatusResponse {
+    id: i32,
+    name: String,
+    slug: String,
+    #[serde(default)]
+    color: String,
+    #[serde(default)]
+    order: i32,
+    is_closed: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArchivePointResponse {
+    id: i32,
+    name: String,
+    value: Option<f64>,
+    #[serde(default)]
+    order: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct ArchiveRoleResponse {
+    id: i32,
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct MilestoneResponse {
+    id: i32,
+    name: String,
+    estimated_start: String,
+    estimated_finish: String,
+    #[serde(default)]
+    closed: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct EpicResponse {
+    id: i32,
+    #[serde(rename = "ref")]
+    reference: i32,
+    subject: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    tags: Vec<(String, Option<String>)>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RelatedStoryResponse {
+    user_story: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct StoryResponse {
+    id: i32,
+    #[serde(rename = "ref")]
+    reference: i32,
+    subject: String,
+    #[serde(default)]
+    description: String,
+    status: i32,
+    #[serde(default)]
+    tags: Vec<(String, Option<String>)>,
+    assigned_users: Vec<i32>,
+    due_date: Option<String>,
+    milestone: Option<i32>,
+    #[serde(default)]
+    points: HashMap<String, i32>,
+    team_requirement: bool,
+    client_requirement: bool,
+    is_blocked: bool,
+    version: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct HistoryResponse {
+    user: HistoryUserResponse,
+    created_at: String,
+    #[serde(default)]
+    comment: String,
+    delete_comment_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct HistoryUserResponse {
+    #[serde(default)]
+    username: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AttachmentResponse {
+    name: String,
+    size: i64,
+    url: String,
+    #[serde(default)]
+    description: String,
+    created_date: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct CreatedResponse {
+    id: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusNewRequest {
+    project: i32,
+    name: String,
+    color: String,
+    order: i32,
+    is_closed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct PointNewRequest {
+    project: i32,
+    name: String,
+    value: Option<f64>,
+    order: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct MilestoneNewRequest {
+    project: i32,
+    name: String,
+    estimated_start: String,
+    estimated_finish: String,
+    closed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct StoryRestoreRequest {
+    project: i32,
+    subject: String,
+    description: String,
+    status: i32,
+    tags: Vec<String>,
+    assigned_users: Vec<i32>,
+    due_date: Option<String>,
+    milestone: Option<i32>,
+    points: HashMap<String, i32>,
+    team_requirement: bool,
+    client_requirement: bool,
+    is_blocked: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct StoryCommentRequest {
+    comment: String,
+    version: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct EpicNewRequest {
+    project: i32,
+    subject: String,
+    description: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct EpicStoryRequest {
+    epic: i32,
+    user_story: i32,
+}
+
+impl Taiga {
+    // Gather everything about a project into a self contained archive
+    pub fn export_archive(&mut self, id: i32) -> Result<ProjectArchive> {
+        let project = self.get::<ArchiveProjectResponse>(&format!("/projects/{}", id))?;
+        let milestones =
+            self.get::<Vec<MilestoneResponse>>(&format!("/milestones?project={}", id))?;
+        let stories = self.get::<Vec<StoryResponse>>(&format!("/userstories?project={}", id))?;
+        let epics = self.get::<Vec<EpicResponse>>(&format!("/epics?project={}", id))?;
+
+        let username = |user_id: &i32| {
+            project
+                .members
+                .iter()
+                .find(|member| member.id == *user_id)
+                .map(|member| member.username.clone())
+        };
+
+        let mut archived_stories = Vec::new();
+        for story in &stories {
+            // the list does not carry descriptions
+            let detail = self.get::<StoryResponse>(&format!("/userstories/{}", story.id))?;
+
+            let comments = self
+                .get::<Vec<HistoryResponse>>(&format!("/history/userstory/{}", story.id))?
+                .into_iter()
+                .filter(|entry| !entry.comment.is_empty() && entry.delete_comment_date.is_none())
+                .map(|entry| ArchiveComment {
+                    author: entry.user.username,
+                    created_at: entry.created_at,
+                    comment: entry.comment,
+                })
+                .collect();
+
+            let attachments = self
+                .get::<Vec<AttachmentResponse>>(&format!(
+                    "/userstories/attachments?object_id={}&project={}",
+                    story.id, id
+                ))?
+                .into_iter()
+                .map(|attachment| ArchiveAttachment {
+                    name: attachment.name,
+                    size: attachment.size,
+                    url: attachment.url,
+                    description: attachment.description,
+                    created_date: attachment.created_date,
+                })
+                .collect();
+
+            let points = detail
+                .points
+                .iter()
+                .filter_map(|(role_id, point_id)| {
+                    let role = project
+                        .roles
+                        .iter()
+                        .find(|role| role.id.to_string() == *role_id)?;
+                    let point = project.points.iter().find(|point| point.id == *point_id)?;
+                    Some((role.name.clone(), point.value))
+                })
+                .collect();
+
+            archived_stories.push(ArchiveStory {
+                reference: detail.reference,
+                subject: detail.subject,
+                description: detail.description,
+                status: project
+                    .us_statuses
+                    .iter()
+                    .find(|status| status.id == detail.status)
+                    .map(|status| status.slug.clone())
+                    .unwrap_or_default(),
+                tags: detail.tags.into_iter().map(|(tag, _)| tag).collect(),
+                assigned: detail.assigned_users.iter().filter_map(username).collect(),
+                due_date: detail.due_date,
+                milestone: detail.milestone.and_then(|milestone_id| {
+                    milestones
+                        .iter()
+                        .find(|milestone| milestone.id == milestone_id)
+                        .map(|milestone| milestone.name.clone())
+                }),
+                points,
+                team: detail.team_requirement,
+                client: detail.client_requirement,
+                blocked: detail.is_blocked,
+                comments,
+                attachments,
+            });
+        }
+
+        let mut archived_epics = Vec::new();
+        for epic in epics {
+            let detail = self.get::<EpicResponse>(&format!("/epics/{}", epic.id))?;
+            let related = self.get::<Vec<RelatedStoryResponse>>(&format!(
+                "/epics/{}/related_userstories",
+                epic.id
+            ))?;
+
+            archived_epics.push(ArchiveEpic {
+                reference: detail.reference,
+                subject: detail.subject,
+                description: detail.description,
+                tags: detail.tags.into_iter().map(|(tag, _)| tag).collect(),
+                stories: related
+                    .iter()
+                    .filter_map(|related| {
+                        stories
+                            .iter()
+                            .find(|story| story.id == related.user_story)
+                            .map(|story| story.reference)
+                    })
+                    .collect(),
+            });
+        }
+
+        Ok(ProjectArchive {
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            project: ArchiveProject {
+                name: project.name,
+                slug: project.slug,
+                description: project.description,
+            },
+            statuses: project
+                .us_statuses
+                .iter()
+                .map(|status| ArchiveStatus {
+                    name: status.name.clone(),
+                    slug: status.slug.clone(),
+                    color: status.color.clone(),
+                    order: status.order,
+                    is_closed: status.is_closed,
+                })
+                .collect(),
+            points: project
+                .points
+                .iter()
+                .map(|point| ArchivePoint {
+                    name: point.name.clone(),
+                    value: point.value,
+                    order: point.order,
+                })
+                .collect(),
+            members: project
+                .members
+                .iter()
+                .map(|member| ArchiveMember {
+                    username: member.username.clone(),
+                    full_name: member.full_name.clone(),
+                    role: member.role_name.clone(),
+                })
+                .collect(),
+            milestones: milestones
+                .iter()
+                .map(|milestone| ArchiveMilestone {
+                    name: milestone.name.clone(),
+                    estimated_start: milestone.estimated_start.clone(),
+                    estimated_finish: milestone.estimated_finish.clone(),
+                    closed: milestone.closed,
+                })
+                .collect(),
+            epics: archived_epics,
+            stories: archived_stories,
+        })
+    }
+
+    // Recreate an archive into a project that has no stories yet
+    pub fn restore_archive(&mut self, id: i32, archive: &ProjectArchive) -> Result<RestoreReport> {
+        if archive.version > ARCHIVE_VERSION {
+            return Err(anyhow!(
+                "Archive version {} is newer than the supported version {}",
+                archive.version,
+                ARCHIVE_VERSION
+            ));
+        }
+
+        let stories = self.get::<Vec<StoryResponse>>(&format!("/userstories?project={}", id))?;
+        if !stories.is_empty() {
+            return Err(anyhow!(
+                "Project already has stories, restore needs an empty project"
+            ));
+        }
+
+        let mut project = self.get::<ArchiveProjectResponse>(&format!("/projects/{}", id))?;
+        let mut report = RestoreReport::default();
+
+        // statuses and points missing from the project are created
+        let mut status_ids = HashMap::new();
+        for status in &archive.statuses {
+            let existing = project
+                .us_statuses
+                .iter()
+                .find(|s| s.slug == status.slug || s.name == status.name);
+            let status_id = match existing {
+                Some(existing) => existing.id,
+                None => {
+                    report.statuses += 1;
+                    self.post::<StatusNewRequest, CreatedResponse>(
+                        "/userstory-statuses",
+                        &StatusNewRequest {
+                            project: id,
+                            name: status.name.clone(),
+                            color: status.color.clone(),
+                            order: status.order,
+                            is_closed: status.is_closed,
+                        },
+                    )?
+                    .id
+                }
+            };
+            status_ids.insert(status.slug.clone(), status_id);
+        }
+
+        for point in &archive.points {
+            if !project.points.iter().any(|p| p.value == point.value) {
+                let created = self.post::<PointNewRequest, CreatedResponse>(
+                    "/points",
+                    &PointNewRequest {
+                        project: id,
+                        name: point.name.clone(),
+                        value: point.value,
+                        order: point.order,
+                    },
+                )?;
+                project.points.push(ArchivePointResponse {
+                    id: created.id,
+                    name: point.name.clone(),
+                    value: point.value,
+                    order: point.order,
+                });
+            }
+        }
+
+        let mut milestone_ids = HashMap::new();
+        for milestone in &archive.milestones {
+            let created = self.post::<MilestoneNewRequest, CreatedResponse>(
+                "/milestones",
+                &MilestoneNewRequest {
+                    project: id,
+                    name: milestone.name.clone(),
+                    estimated_start: milestone.estimated_start.clone(),
+                    estimated_finish: milestone.estimated_finish.clone(),
+                    closed: milestone.closed,
+                },
+            )?;
+            milestone_ids.insert(milestone.name.clone(), created.id);
+            report.milestones += 1;
+        }
+
+        let default_status = project
+            .us_statuses
+            .first()
+            .map(|status| status.id)
+            .ok_or_else(|| anyhow!("Project has no status"))?;
+
+        let mut story_ids = HashMap::new();
+        let mut archived_stories = archive.stories.iter().collect::<Vec<&ArchiveStory>>();
+        archived_stories.sort_by_key(|story| story.reference);
+
+        let mapping = RestoreMapping::new(id, &project, status_ids, default_status, milestone_ids);
+
+        for story in archived_stories {
+            let (request, missing) = mapping.story_request(story);
+            for username in missing {
+                if !report.missing_members.contains(&username) {
+                    report.missing_members.push(username);
+                }
+            }
+
+            let mut created =
+                self.post::<StoryRestoreRequest, StoryResponse>("/userstories", &request)?;
+            report.stories += 1;
+
+            // comments cannot be backdated, so their author and date are kept in the text
+            for comment in &story.comments {
+                created = self.patch::<StoryCommentRequest, StoryResponse>(
+                    &format!("/userstories/{}", created.id),
+                    &StoryCommentRequest {
+                        comment: format!(
+                            "*{} on {}:*\n\n{}",
+                            comment.author, comment.created_at, comment.comment
+                        ),
+                        version: created.version,
+                    },
+                )?;
+                report.comments += 1;
+            }
+
+            report.skipped_attachments += story.attachments.len();
+            story_ids.insert(story.reference, created.id);
+        }
+
+        for epic in &archive.epics {
+            let created = self.post::<EpicNewRequest, CreatedResponse>(
+                "/epics",
+                &EpicNewRequest {
+                    project: id,
+                    subject: epic.subject.clone(),
+                    description: epic.description.clone(),
+                    tags: epic.tags.clone(),
+                },
+            )?;
+
+            for reference in &epic.stories {
+                if let Some(story_id) = story_ids.get(reference) {
+                    self.post::<EpicStoryRequest, serde_json::Value>(
+                        &format!("/epics/{}/related_userstories", created.id),
+                        &EpicStoryRequest {
+                            epic: created.id,
+                            user_story: *story_id,
+                        },
+                    )?;
+                }
+            }
+            report.epics += 1;
+        }
+
+        Ok(report)
+    }
+}
+
+// Where the statuses, milestones, members and points of an archive land in the target project
+struct RestoreMapping<'a> {
+    id: i32,
+    project: &'a ArchiveProjectResponse,
+    members: Vec<TaigaUser>,
+    status_ids: HashMap<String, i32>,
+    default_status: i32,
+    milestone_ids: HashMap<String, i32>,
+}
+
+impl<'a> RestoreMapping<'a> {
+    fn new(
+        id: i32,
+        project: &'a ArchiveProjectResponse,
+        status_ids: HashMap<String, i32>,
+        default_status: i32,
+        milestone_ids: HashMap<String, i32>,
+    ) -> Self {
+        // matched the same way as the users given on the command line
+        let members = project
+            .members
+            .iter()
+            .map(|member| TaigaUser {
+                id: member.id,
+                username: member.username.clone(),
+                full_name: member.full_name.clone(),
+                role: member.role_name.clone(),
+                email: None,
+                is_active: true,
+            })
+            .collect();
+
+        RestoreMapping {
+            id,
+            project,
+            members,
+            status_ids,
+            default_status,
+            milestone_ids,
+        }
+    }
+
+    // The request recreating an archived story, with the assignees that are not members
+    fn story_request(&self, story: &ArchiveStory) -> (StoryRestoreRequest, Vec<String>) {
+        let mut assigned = Vec::new();
+        let mut missing = Vec::new();
+        for username in &story.assigned {
+            match find_member(&self.members, username) {
+                Ok(member) => assigned.push(member.id),
+                Err(_) => missing.push(username.clone()),
+            }
+        }
+
+        let points = story
+            .points
+            .iter()
+            .filter_map(|(role_name, value)| {
+                let role = self
+                    .project
+                    .roles
+                    .iter()
+                    .find(|role| role.name == *role_name)?;
+                let point = self
+                    .project
+                    .points
+                    .iter()
+                    .find(|point| point.value == *value)?;
+                Some((role.id.to_string(), point.id))
+            })
+            .collect();
+
+        let request = StoryRestoreRequest {
+            project: self.id,
+            subject: story.subject.clone(),
+            description: story.description.clone(),
+            status: self
+                .status_ids
+                .get(&story.status)
+                .copied()
+                .unwrap_or(self.default_status),
+            tags: story.tags.clone(),
+            assigned_users: assigned,
+            due_date: story.due_date.clone(),
+            milestone: story
+                .milestone
+                .as_ref()
+                .and_then(|name| self.milestone_ids.get(name).copied()),
+            points,
+            team_requirement: story.team,
+            client_requirement: story.client,
+            is_blocked: story.blocked,
+        };
+
+        (request, missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn archive_json() -> serde_json::Value {
+        json!({
+            "version": 1,
+            "exported_at": "2026-10-01T08:00:00Z",
+            "project": { "name": "Web", "slug": "web", "description": "" },
+            "statuses": [
+                { "name": "New", "slug": "new", "color": "#999", "order": 1, "is_closed": false },
+                { "name": "Done", "slug": "done", "color": "#0f0", "order": 2, "is_closed": true }
+            ],
+            "points": [{ "name": "3", "value": 3.0, "order": 3 }],
+            "members": [{ "username": "alice", "full_name": "Alice Martin", "role": "Dev" }],
+            "milestones": [],
+            "epics": [{ "reference": 1, "subject": "Login", "description": "", "tags": [], "stories": [2] }],
+            "stories": [{
+                "reference": 2,
+                "subject": "Fix login",
+                "description": "Fails on Safari",
+                "status": "done",
+                "tags": ["bug"],
+                "assigned": ["alice", "bob"],
+                "due_date": "2026-10-20",
+                "milestone": "Sprint 1",
+                "points": { "Dev": 3.0, "Design": 5.0 },
+                "team": false,
+                "client": true,
+                "blocked": false,
+                "comments": [{ "author": "alice", "created_at": "2026-10-02", "comment": "On it" }],
+                "attachments": []
+            }]
+        })
+    }
+
+    fn project() -> ArchiveProjectResponse {
+        serde_json::from_value(json!({
+            "name": "Web",
+            "slug": "web",
+            "members": [
+                { "id": 7, "username": "alice", "full_name": "Alice Martin", "role_name": "Dev" },
+                { "id": 8, "username": "carol" }
+            ],
+            "us_statuses": [
+                { "id": 10, "name": "New", "slug": "new", "is_closed": false },
+                { "id": 11, "name": "Done", "slug": "done", "is_closed": true }
+            ],
+            "points": [{ "id": 20, "name": "3", "value": 3.0 }],
+            "roles": [{ "id": 30, "name": "Dev" }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn archives_survive_a_round_trip() {
+        let archive: ProjectArchive = serde_json::from_value(archive_json()).unwrap();
+        let text = serde_json::to_string_pretty(&archive).unwrap();
+        let read: ProjectArchive = serde_json::from_str(&text).unwrap();
+
+        assert_eq!(serde_json::to_value(&read).unwrap(), archive_json());
+        assert_eq!(read.stories[0].assigned, vec!["alice", "bob"]);
+        assert_eq!(read.epics[0].stories, vec![2]);
+    }
+
+    #[test]
+    fn maps_stories_onto_the_target_project() {
+        let archive: ProjectArchive = serde_json::from_value(archive_json()).unwrap();
+        let project = project();
+        let status_ids = HashMap::from([("new".to_string(), 10), ("done".to_string(), 11)]);
+        let milestone_ids = HashMap::from([("Sprint 1".to_string(), 40)]);
+        let mapping = RestoreMapping::new(5, &project, status_ids, 10, milestone_ids);
+
+        let (request, missing) = mapping.story_request(&archive.stories[0]);
+        assert_eq!(request.project, 5);
+        assert_eq!(request.status, 11);
+        assert_eq!(request.assigned_users, vec![7]);
+        assert_eq!(missing, vec!["bob"]);
+        assert_eq!(request.milestone, Some(40));
+        // the Design role does not exist in the target project
+        assert_eq!(request.points, HashMap::from([("30".to_string(), 20)]));
+        assert!(request.client_requirement && !request.team_requirement);
+    }
+
+    #[test]
+    fn unknown_statuses_fall_back_to_the_first_one() {
+        let mut archive: ProjectArchive = serde_json::from_value(archive_json()).unwrap();
+        archive.stories[0].status = "review".to_string();
+        archive.stories[0].milestone = None;
+        let project = project();
+        let mapping = RestoreMapping::new(5, &project, HashMap::new(), 10, HashMap::new());
+
+        let (request, _) = mapping.story_request(&archive.stories[0]);
+        assert_eq!(request.status, 10);
+        assert_eq!(request.milestone, None);
+    }
+}
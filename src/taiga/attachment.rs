@@ -0,0 +1,77 @@
+use anyhow::{Context, Result};
+use reqwest::blocking::multipart::Form;
+use serde::Deserialize;
+use std::io::Write;
+
+use super::Taiga;
+
+#[derive(Debug, Clone)]
+pub struct TaigaAttachment {
+    pub name: String,
+    pub size: i64,
+    pub url: String,
+    pub owner: Option<i32>,
+    pub created: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AttachmentResponse {
+    name: String,
+    size: i64,
+    url: String,
+    owner: Option<i32>,
+    created_date: String,
+}
+
+impl Taiga {
+    pub fn get_attachments(
+        &mut self,
+        project_id: i32,
+        task_id: i32,
+    ) -> Result<Vec<TaigaAttachment>> {
+        self.get::<Vec<AttachmentResponse>>(&format!(
+            "/userstories/attachments?object_id={}&project={}",
+            task_id, project_id
+        ))
+        .map(|attachments| attachments.iter().map(TaigaAttachment::new).collect())
+    }
+
+    pub fn upload_attachment(
+        &mut self,
+        project_id: i32,
+        task_id: i32,
+        path: &str,
+    ) -> Result<TaigaAttachment> {
+        self.post_multipart::<AttachmentResponse, _>("/userstories/attachments", || {
+            Form::new()
+                .text("project", project_id.to_string())
+                .text("object_id", task_id.to_string())
+                .file("attached_file", path)
+                .with_context(|| format!("Could not read {}", path))
+        })
+        .map(|attachment| TaigaAttachment::new(&attachment))
+    }
+
+    pub fn download_attachment<W>(
+        &mut self,
+        attachment: &TaigaAttachment,
+        writer: &mut W,
+    ) -> Result<u64>
+    where
+        W: Write,
+    {
+        self.download(&attachment.url, writer)
+    }
+}
+
+impl TaigaAttachment {
+    fn new(attachment: &AttachmentResponse) -> TaigaAttachment {
+        TaigaAttachment {
+            name: attachment.name.clone(),
+            size: attachment.size,
+            url: attachment.url.clone(),
+            owner: attachment.owner,
+            created: attachment.created_date.clone(),
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use super::Taiga;
+use crate::utils::slug;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaAttribute {
+    pub id: i32,
+    pub name: String,
+    pub kind: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AttributeResponse {
+    id: i32,
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct AttributeValuesResponse {
+    #[serde(default)]
+    attributes_values: Map<String, Value>,
+    version: i32,
+}
+
+#[derive(Serialize, Debug)]
+struct AttributeValuesRequest {
+    attributes_values: Map<String, Value>,
+    version: i32,
+}
+
+impl TaigaAttribute {
+    // Convert a value given on the command line to the JSON type of the attribute
+    pub fn parse_value(&self, value: &str) -> Result<Value> {
+        if value.is_empty() {
+            return Ok(Value::Null);
+        }
+
+        match self.kind.as_str() {
+            "number" => {
+                if let Ok(number) = value.parse::<i64>() {
+                    return Ok(Value::from(number));
+                }
+                value
+                    .parse::<f64>()
+                    .map(Value::from)
+                    .map_err(|_| anyhow!("{} is not a number", value))
+            }
+            "checkbox" => match value.to_lowercase().as_str() {
+                "true" | "yes" | "y" | "1" => Ok(Value::Bool(true)),
+                "false" | "no" | "n" | "0" => Ok(Value::Bool(false)),
+                _ => Err(anyhow!("{} is not yes or no", value)),
+            },
+            "date" => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(|_| Value::String(value.to_string()))
+                .map_err(|_| anyhow!("{} is not a date like 2024-12-31", value)),
+            _ => Ok(Value::String(value.to_string())),
+        }
+    }
+
+    // Whether a stored value is the one given on the command line, an empty value meaning unset
+    pub fn matches(&self, stored: Option<&String>, value: &str) -> bool {
+        let value = match self.parse_value(value) {
+            Ok(value) => value_text(&value),
+            Err(_) => Some(value.to_string()),
+        };
+
+        match (stored, value) {
+            (None, None) => true,
+            (Some(stored), Some(value)) => stored.to_lowercase() == value.to_lowercase(),
+            _ => false,
+        }
+    }
+}
+
+// Find an attribute by name, ignoring the case and punctuation
+pub fn find_attribute<'a>(
+    attributes: &'a [TaigaAttribute],
+    name: &str,
+) -> Option<&'a TaigaAttribute> {
+    let name = slug(name.replace('-', " "));
+    attributes
+        .iter()
+        .find(|attribute| slug(attribute.name.clone()) == name)
+}
+
+// Values are kept as text, the way they are shown and filtered on
+fn value_text(value: &Value) -> Option<String> {
+    let value = match value {
+        Value::Null => return None,
+        Value::String(value) => value.clone(),
+        Value::Bool(true) => "yes".to_string(),
+        Value::Bool(false) => "no".to_string(),
+        value => value.to_string(),
+    };
+    Some(value).filter(|value| !value.is_empty())
+}
+
+fn format_values(values: &Map<String, Value>) -> HashMap<i32, String> {
+    values
+        .iter()
+        .filter_map(|(id, value)| Some((id.parse().ok()?, value_text(value)?)))
+        .collect()
+}
+
+impl Taiga {
+    pub fn get_attributes(&mut self, project_id: i32) -> Result<Vec<TaigaAttribute>> {
+        self.get::<Vec<AttributeResponse>>(&format!(
+            "/userstory-custom-attributes?project={}",
+            project_id
+        ))
+        .map(|attributes| {
+            attributes
+                .into_iter()
+                .map(|a| TaigaAttribute {
+                    id: a.id,
+                    name: a.name,
+                    kind: a.kind,
+                })
+                .collect()
+        })
+    }
+
+    pub fn get_attribute_values(&mut self, task_id: i32) -> Result<HashMap<i32, String>> {
+        self.get::<AttributeValuesResponse>(&format!(
+            "/userstories/custom-attributes-values/{}",
+            task_id
+        ))
+        .map(|values| format_values(&values.attributes_values))
+    }
+
+    // Set some custom attributes of a story, keeping the others
+    pub fn set_attribute_values(
+        &mut self,
+        task_id: i32,
+        values: Vec<(i32, Value)>,
+    ) -> Result<HashMap<i32, String>> {
+        let endpoint = format!("/userstories/custom-attributes-values/{}", task_id);
+        let mut current = self.get::<AttributeValuesResponse>(&endpoint)?;
+        for (id, value) in values {
+            current.attributes_values.insert(id.to_string(), value);
+        }
+
+        self.patch::<AttributeValuesRequest, AttributeValuesResponse>(
+            &endpoint,
+            &AttributeValuesRequest {
+                attributes_values: current.attributes_values,
+                version: current.version,
+            },
+        )
+        .map(|values| format_values(&values.attributes_values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(kind: &str) -> TaigaAttribute {
+        TaigaAttribute {
+            id: 3,
+            name: "Time Spent".to_string(),
+            kind: kind.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_values_to_the_attribute_type() {
+        assert_eq!(
+            attribute("number").parse_value("3").unwrap(),
+            Value::from(3)
+        );
+        assert_eq!(
+            attribute("number").parse_value("2.5").unwrap(),
+            Value::from(2.5)
+        );
+        assert!(attribute("number").parse_value("soon").is_err());
+
+        assert_eq!(
+            attribute("checkbox").parse_value("Yes").unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            attribute("checkbox").parse_value("0").unwrap(),
+            Value::Bool(false)
+        );
+        assert!(attribute("checkbox").parse_value("maybe").is_err());
+
+        assert_eq!(
+            attribute("date").parse_value("2024-12-31").unwrap(),
+            Value::from("2024-12-31")
+        );
+        assert!(attribute("date").parse_value("31/12/2024").is_err());
+
+        assert_eq!(
+            attribute("text").parse_value("3h").unwrap(),
+            Value::from("3h")
+        );
+        assert_eq!(attribute("number").parse_value("").unwrap(), Value::Null);
+    }
+
+    #[test]
+    fn matches_stored_values() {
+        let stored = |value: &str| Some(value.to_string());
+
+        assert!(attribute("text").matches(stored("Backend").as_ref(), "backend"));
+        assert!(!attribute("text").matches(stored("Backend").as_ref(), "front"));
+        assert!(attribute("checkbox").matches(stored("yes").as_ref(), "true"));
+        assert!(attribute("number").matches(stored("3").as_ref(), "3"));
+
+        // an empty value is an unset attribute
+        assert!(attribute("text").matches(None, ""));
+        assert!(!attribute("text").matches(stored("Backend").as_ref(), ""));
+        assert!(!attribute("text").matches(None, "backend"));
+    }
+
+    #[test]
+    fn finds_attributes_ignoring_case_and_punctuation() {
+        let attributes = vec![attribute("number")];
+
+        assert_eq!(
+            find_attribute(&attributes, "time-spent").map(|a| a.id),
+            Some(3)
+        );
+        assert_eq!(
+            find_attribute(&attributes, "TIME SPENT").map(|a| a.id),
+            Some(3)
+        );
+        assert!(find_attribute(&attributes, "estimate").is_none());
+        assert!(find_attribute(&[], "time spent").is_none());
+    }
+
+    #[test]
+    fn formats_typed_values_as_text() {
+        let values: Map<String, Value> = serde_json::from_value(serde_json::json!({
+            "1": "Backend",
+            "2": 2.5,
+            "3": true,
+            "4": null,
+            "5": "",
+            "x": "ignored"
+        }))
+        .unwrap();
+
+        let values = format_values(&values);
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[&1], "Backend");
+        assert_eq!(values[&2], "2.5");
+        assert_eq!(values[&3], "yes");
+    }
+}
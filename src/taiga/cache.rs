@@ -1,12 +1,29 @@
 use anyhow::{anyhow, Context, Result};
 use directories::ProjectDirs;
+use serde::Deserialize;
 use sha1::{Digest, Sha1};
+use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::PathBuf;
 use std::process::exit;
-
-use super::{Taiga, TaigaProject, TaigaTask, TaigaTasks};
+use std::time::SystemTime;
+
+use super::{
+    Taiga, TaigaActivity, TaigaProject, TaigaSyncState, TaigaTask, TaigaTasks, TaigaUuids,
+};
+
+// The leading fields of the Taiga config, which do not depend on the project layout
+#[derive(Deserialize)]
+struct TaigaLogin {
+    auth_token: String,
+    refresh: String,
+    refresh_time: SystemTime,
+    url: String,
+    id: i32,
+    username: String,
+    password: String,
+}
 
 impl Taiga {
     pub fn from_cache() -> Option<Self> {
@@ -30,8 +47,27 @@ impl Taiga {
             return None;
         }
 
-        match bincode::deserialize(&buffer) {
-            Ok(taiga) => Some(taiga),
+        if let Ok(taiga) = bincode::deserialize(&buffer) {
+            return Some(taiga);
+        }
+
+        // a config written by another version keeps the login and fetches the projects again
+        match bincode::deserialize::<TaigaLogin>(&buffer) {
+            Ok(login) => {
+                let mut taiga = Taiga {
+                    auth_token: login.auth_token,
+                    refresh: login.refresh,
+                    refresh_time: login.refresh_time,
+                    url: login.url,
+                    id: login.id,
+                    username: login.username,
+                    password: login.password,
+                    projects: Vec::new(),
+                };
+                taiga.projects = taiga.get_projects().ok()?;
+                taiga.save_cache().ok()?;
+                Some(taiga)
+            }
             Err(_) => {
                 eprintln!("Config file is corrupted. Please log in.");
                 None
@@ -93,10 +129,17 @@ impl Taiga {
                     tasks
                 }
             }
-            _ => {
-                eprintln!("Invalid task id for this project");
-                exit(1);
-            }
+            // without a search yet, the project is fetched and no id refers to a task
+            None => self.update_tasks(
+                id,
+                TaigaTasks {
+                    id,
+                    tasks: Vec::new(),
+                    members: Vec::new(),
+                    statuses: Vec::new(),
+                    attributes: Vec::new(),
+                },
+            ),
         }
     }
 }
@@ -182,10 +225,9 @@ impl TaigaTasks {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer)
             .expect("Could not read cache file");
-        let tasks =
-            bincode::deserialize::<Self>(&buffer[..]).expect("Could not deserialize cache file");
 
-        Some(tasks)
+        // a cache written by an older version is outdated and gets refreshed
+        bincode::deserialize::<Self>(&buffer[..]).ok()
     }
 
     pub fn save_cache(self) {
@@ -206,3 +248,123 @@ impl TaigaTasks {
             .expect("Could not save cache");
     }
 }
+
+impl TaigaUuids {
+    fn cache_path(id: i32) -> PathBuf {
+        let project_dirs =
+            ProjectDirs::from("", "", "taiga").expect("Could not get standard directories");
+        let cache_dir = project_dirs.cache_dir();
+        fs::create_dir_all(cache_dir).expect("Could not create parent directories");
+
+        let mut hasher = Sha1::new();
+        hasher.update(format!("uuids-{}", id).as_bytes());
+        let hash = hasher.finalize();
+
+        cache_dir.join(format!("{:x}", hash))
+    }
+
+    pub fn from_cache(id: i32) -> Self {
+        let path = Self::cache_path(id);
+
+        let empty = TaigaUuids {
+            id,
+            uuids: HashMap::new(),
+        };
+
+        if !path.exists() {
+            return empty;
+        }
+
+        let mut file = File::open(&path).expect("Could not open cache file");
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)
+            .expect("Could not read cache file");
+        // an outdated cache starts over, matching stories on their taigaref only
+        bincode::deserialize::<Self>(&buffer[..]).unwrap_or(empty)
+    }
+
+    pub fn save_cache(&self) {
+        let path = Self::cache_path(self.id);
+        let serialized_data = bincode::serialize(self).expect("Serialization failed");
+        let mut file = File::create(path).expect("Could not create cache file");
+        file.write_all(&serialized_data)
+            .expect("Could not save cache");
+    }
+}
+
+impl TaigaSyncState {
+    fn cache_path(id: i32, path: &str) -> PathBuf {
+        let project_dirs =
+            ProjectDirs::from("", "", "taiga").expect("Could not get standard directories");
+        let cache_dir = project_dirs.cache_dir();
+        fs::create_dir_all(cache_dir).expect("Could not create parent directories");
+
+        let mut hasher = Sha1::new();
+        hasher.update(format!("sync-{}-{}", id, path).as_bytes());
+        let hash = hasher.finalize();
+
+        cache_dir.join(format!("{:x}", hash))
+    }
+
+    pub fn from_cache(id: i32, path: &str) -> Self {
+        let cache_path = Self::cache_path(id, path);
+
+        let empty = TaigaSyncState {
+            id,
+            path: path.to_string(),
+            stories: Vec::new(),
+        };
+
+        if !cache_path.exists() {
+            return empty;
+        }
+
+        let mut file = File::open(&cache_path).expect("Could not open cache file");
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)
+            .expect("Could not read cache file");
+        // an outdated cache makes the next sync a first one
+        bincode::deserialize::<Self>(&buffer[..]).unwrap_or(empty)
+    }
+
+    pub fn save_cache(&self) {
+        let cache_path = Self::cache_path(self.id, &self.path);
+        let serialized_data = bincode::serialize(self).expect("Serialization failed");
+        let mut file = File::create(cache_path).expect("Could not create cache file");
+        file.write_all(&serialized_data)
+            .expect("Could not save cache");
+    }
+}
+
+impl TaigaActivity {
+    fn cache_path() -> PathBuf {
+        let project_dirs =
+            ProjectDirs::from("", "", "taiga").expect("Could not get standard directories");
+        let cache_dir = project_dirs.cache_dir();
+        fs::create_dir_all(cache_dir).expect("Could not create parent directories");
+
+        cache_dir.join("activity")
+    }
+
+    pub fn from_cache() -> Option<Self> {
+        let path = Self::cache_path();
+
+        if !path.exists() {
+            return None;
+        }
+
+        let mut file = File::open(&path).expect("Could not open cache file");
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)
+            .expect("Could not read cache file");
+        bincode::deserialize::<Self>(&buffer[..]).ok()
+    }
+
+    pub fn save_cache(&self) {
+        let path = Self::cache_path();
+        let serialized_data = bincode::serialize(self).expect("Serialization failed");
+        let mut file = File::create(path).expect("Could not create cache file");
+        file.write_all(&serialized_data)
+            .expect("Could not save cache");
+    }
+}
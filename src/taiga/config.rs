@@ -0,0 +1,108 @@
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TaigaConfig {
+    pub default_project: Option<String>,
+    pub aliases: HashMap<String, String>,
+    pub templates: HashMap<String, TaigaTemplate>,
+    pub bulk_confirm: usize,
+    pub wip_limits: WipPolicy,
+}
+
+// What moving a story into a status at its WIP limit does
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum WipPolicy {
+    #[default]
+    Warn,
+    Refuse,
+}
+
+impl Default for TaigaConfig {
+    fn default() -> Self {
+        TaigaConfig {
+            default_project: None,
+            aliases: HashMap::new(),
+            templates: HashMap::new(),
+            bulk_confirm: 3,
+            wip_limits: WipPolicy::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct TaigaTemplate {
+    pub prefix: Option<String>,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
+    pub status: Option<String>,
+    pub assign: Vec<String>,
+    pub team: Option<bool>,
+    pub client: Option<bool>,
+    pub block: Option<bool>,
+    pub points: Option<f64>,
+}
+
+impl TaigaConfig {
+    // Load the user configuration, falling back to defaults when there is none
+    pub fn load() -> Result<Self> {
+        let path = match Self::config_path() {
+            Some(path) => path,
+            None => return Ok(Self::default()),
+        };
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context("Could not read config file")?;
+        toml::from_str(&content).context("Could not parse config file")
+    }
+
+    // Directory holding the user hooks, next to the config file
+    pub fn hooks_dir() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "taiga").map(|proj_dirs| proj_dirs.config_dir().join("hooks"))
+    }
+
+    fn config_path() -> Option<PathBuf> {
+        ProjectDirs::from("", "", "taiga")
+            .map(|proj_dirs| proj_dirs.config_dir().join("config.toml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_templates_with_defaults() {
+        let config: TaigaConfig = toml::from_str(
+            r#"
+            [templates.bug]
+            prefix = "[bug] "
+            tags = ["bug"]
+            points = 3
+
+            [templates.chore]
+            "#,
+        )
+        .unwrap();
+
+        let bug = &config.templates["bug"];
+        assert_eq!(bug.prefix.as_deref(), Some("[bug] "));
+        assert_eq!(bug.tags, vec!["bug"]);
+        assert_eq!(bug.points, Some(3.0));
+        assert_eq!(bug.client, None);
+
+        let chore = &config.templates["chore"];
+        assert!(chore.prefix.is_none() && chore.tags.is_empty() && chore.assign.is_empty());
+        assert_eq!(config.bulk_confirm, 3);
+    }
+}
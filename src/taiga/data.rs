@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::{de::DeserializeOwned, Serialize};
+use sha1::{Digest, Sha1};
+use std::fs;
+use std::path::PathBuf;
+
+use super::{TaigaRecurrences, TaigaTimeLog};
+
+// What the user records through taiga-cli is kept as JSON in the data directory, unlike the
+// caches, so that clearing the cache or upgrading taiga-cli does not lose it
+fn data_path(name: &str) -> Result<PathBuf> {
+    let project_dirs =
+        ProjectDirs::from("", "", "taiga").context("Could not get standard directories")?;
+
+    let data_dir = project_dirs.data_dir();
+    fs::create_dir_all(data_dir).context("Could not create data directory")?;
+
+    Ok(data_dir.join(name))
+}
+
+// Where older versions kept the same data, in the cache directory
+fn legacy_path(name: &str) -> Option<PathBuf> {
+    let project_dirs = ProjectDirs::from("", "", "taiga")?;
+
+    Some(project_dirs.cache_dir().join(name))
+}
+
+// The cache file name of a key, as older versions hashed it
+fn legacy_hash(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+
+    format!("{:x}", hasher.finalize())
+}
+
+fn load_data<T: DeserializeOwned>(name: &str, legacy_name: &str) -> Result<Option<T>> {
+    let path = data_path(name)?;
+
+    if path.exists() {
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        return serde_json::from_str(&content)
+            .with_context(|| format!("Could not parse {}", path.display()))
+            .map(Some);
+    }
+
+    // the file of an older version is taken over while it still decodes
+    Ok(legacy_path(legacy_name)
+        .and_then(|path| fs::read(path).ok())
+        .and_then(|buffer| bincode::deserialize(&buffer).ok()))
+}
+
+fn save_data<T: Serialize>(name: &str, data: &T) -> Result<()> {
+    let path = data_path(name)?;
+
+    let content = serde_json::to_string_pretty(data).context("Could not serialize data")?;
+
+    // written next to it first, so that a failed write keeps the previous data
+    let partial = path.with_extension("partial");
+    fs::write(&partial, content)
+        .with_context(|| format!("Could not write {}", partial.display()))?;
+    fs::rename(&partial, &path).with_context(|| format!("Could not write {}", path.display()))
+}
+
+impl TaigaRecurrences {
+    pub fn load(id: i32) -> Result<Self> {
+        let recurrences = load_data(
+            &format!("recurrences-{}.json", id),
+            &legacy_hash(&format!("recur-{}", id)),
+        )?;
+
+        Ok(recurrences.unwrap_or(TaigaRecurrences {
+            id,
+            recurrences: Vec::new(),
+        }))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data(&format!("recurrences-{}.json", self.id), self)
+    }
+}
+
+impl TaigaTimeLog {
+    pub fn load() -> Result<Self> {
+        Ok(load_data("timelog.json", "timelog")?.unwrap_or_default())
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data("timelog.json", self)
+    }
+}
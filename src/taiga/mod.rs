@@ -1,18 +1,34 @@
+pub mod activity;
+pub mod archive;
+pub mod attachment;
+pub mod attribute;
 pub mod auth;
 pub mod cache;
+pub mod config;
+pub mod data;
 pub mod project;
+pub mod recur;
 pub mod request;
 pub mod status;
+pub mod swimlane;
+pub mod sync;
+#[allow(clippy::module_inception)]
 pub mod taiga;
 pub mod task;
+pub mod timelog;
 pub mod user;
+pub mod webhook;
 
-pub use self::auth::*;
-pub use self::cache::*;
+pub use self::activity::*;
+pub use self::archive::*;
+pub use self::attribute::*;
+pub use self::config::*;
 pub use self::project::*;
-pub use self::request::*;
+pub use self::recur::*;
 pub use self::status::*;
-pub use self::taiga::*;
+pub use self::swimlane::*;
+pub use self::sync::*;
 pub use self::taiga::*;
 pub use self::task::*;
+pub use self::timelog::*;
 pub use self::user::*;
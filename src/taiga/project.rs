@@ -1,10 +1,16 @@
 use anyhow::anyhow;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::Taiga;
+use super::TaigaAttribute;
+use super::TaigaConfig;
 use super::TaigaStatus;
+use super::TaigaSwimlane;
+use super::TaigaTasks;
 use super::TaigaUser;
+use crate::utils::{levenshtein, slug};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TaigaProject {
@@ -12,6 +18,8 @@ pub struct TaigaProject {
     pub name: String,
     pub members: Vec<TaigaUser>,
     pub statuses: Vec<TaigaStatus>,
+    pub attributes: Vec<TaigaAttribute>,
+    pub swimlanes: Vec<TaigaSwimlane>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -25,8 +33,14 @@ pub struct ProjectsResponse {
 struct ProjectResponse {
     id: i32,
     name: String,
+    #[serde(default)]
+    slug: String,
     members: Vec<MemberResponse>,
     us_statuses: Vec<Status>,
+    #[serde(default)]
+    points: Vec<PointResponse>,
+    #[serde(default)]
+    roles: Vec<RoleResponse>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -34,6 +48,66 @@ struct ProjectResponse {
 struct MemberResponse {
     id: i32,
     username: String,
+    #[serde(default)]
+    full_name_display: Option<String>,
+    #[serde(default)]
+    role_name: Option<String>,
+    #[serde(default = "default_active")]
+    is_active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl MemberResponse {
+    // Emails come from the memberships, only for those the user is allowed to see
+    fn user(&self, emails: &[(i32, String)]) -> TaigaUser {
+        TaigaUser {
+            id: self.id,
+            username: self.username.clone(),
+            full_name: self.full_name_display.clone().unwrap_or_default(),
+            role: self.role_name.clone().unwrap_or_default(),
+            email: emails
+                .iter()
+                .find(|(user_id, _)| *user_id == self.id)
+                .map(|(_, email)| email.clone()),
+            is_active: self.is_active,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct PointResponse {
+    id: i32,
+    value: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RoleResponse {
+    id: i32,
+    computable: bool,
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct TemplateResponse {
+    id: i32,
+    slug: String,
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ProjectCreatedResponse {
+    id: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectNewRequest {
+    name: String,
+    description: String,
+    creation_template: i32,
 }
 
 #[derive(Deserialize, Debug)]
@@ -42,6 +116,14 @@ struct Status {
     id: i32,
     slug: String,
     is_closed: bool,
+    #[serde(default)]
+    color: String,
+    #[serde(default)]
+    order: i32,
+    #[serde(default)]
+    wip_limit: Option<i32>,
+    #[serde(default)]
+    is_archived: bool,
 }
 
 impl Taiga {
@@ -50,7 +132,66 @@ impl Taiga {
             .iter()
             .find(|p| p.name == name)
             .ok_or_else(|| anyhow!("Project '{}' not found", name))
-            .map(|project| project.clone())
+            .cloned()
+    }
+
+    // Resolve a project from its name, slug, alias or a unique prefix of its slug
+    pub fn resolve_project(&self, name: &str, config: &TaigaConfig) -> Result<TaigaProject> {
+        let name = config.aliases.get(name).map(String::as_str).unwrap_or(name);
+        // slug drops the dashes, so a slug given by the user is read as words
+        let name_slug = slug(name.replace('-', " "));
+
+        if let Some(project) = self
+            .projects
+            .iter()
+            .find(|p| p.name == name || slug(p.name.clone()) == name_slug)
+        {
+            return Ok(project.clone());
+        }
+
+        let candidates: Vec<&TaigaProject> = if name_slug.is_empty() {
+            Vec::new()
+        } else {
+            self.projects
+                .iter()
+                .filter(|p| slug(p.name.clone()).starts_with(&name_slug))
+                .collect()
+        };
+
+        match candidates.as_slice() {
+            [project] => Ok((*project).clone()),
+            [] => {
+                let suggestion = self
+                    .projects
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .chain(config.aliases.keys().cloned())
+                    .map(|candidate| {
+                        let distance = levenshtein(&name_slug, &slug(candidate.clone()));
+                        (distance, candidate)
+                    })
+                    .filter(|(distance, _)| *distance <= (name_slug.len() / 3).max(2))
+                    .min_by_key(|(distance, _)| *distance);
+
+                match suggestion {
+                    Some((_, candidate)) => Err(anyhow!(
+                        "Project '{}' not found, did you mean '{}'?",
+                        name,
+                        candidate
+                    )),
+                    None => Err(anyhow!("Project '{}' not found", name)),
+                }
+            }
+            candidates => Err(anyhow!(
+                "Project '{}' is ambiguous, could be: {}",
+                name,
+                candidates
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )),
+        }
     }
 
     pub fn get_projects(&mut self) -> Result<Vec<TaigaProject>> {
@@ -62,12 +203,19 @@ impl Taiga {
                         name: p.name.clone(),
                         members: Vec::new(),
                         statuses: Vec::new(),
+                        attributes: Vec::new(),
+                        swimlanes: Vec::new(),
                     })
                     .collect()
             })
     }
 
     pub fn get_project(&mut self, id: i32) -> Result<TaigaProject> {
+        // custom attributes only matter to a few commands, so they may not stop the others
+        let attributes = self.get_attributes(id).unwrap_or_default();
+        // servers older than Taiga 6.6 have no swimlanes
+        let swimlanes = self.get_swimlanes(id).unwrap_or_default();
+        let emails = self.get_member_emails(id).unwrap_or_default();
         self.get::<ProjectResponse>(&format!("/projects/{}", id))
             .map(|p| TaigaProject {
                 id: p.id,
@@ -75,20 +223,242 @@ impl Taiga {
                 members: p
                     .members
                     .iter()
-                    .map(|member| TaigaUser {
-                        id: member.id,
-                        username: member.username.clone(),
-                    })
-                    .collect(),
-                statuses: p
-                    .us_statuses
-                    .iter()
-                    .map(|status| TaigaStatus {
-                        id: status.id,
-                        slug: status.slug.clone(),
-                        is_closed: status.is_closed,
-                    })
+                    .map(|member| member.user(&emails))
                     .collect(),
+                statuses: {
+                    let mut statuses: Vec<TaigaStatus> = p
+                        .us_statuses
+                        .iter()
+                        .map(|status| TaigaStatus {
+                            id: status.id,
+                            slug: status.slug.clone(),
+                            is_closed: status.is_closed,
+                            color: status.color.clone(),
+                            order: status.order,
+                            wip_limit: status.wip_limit,
+                            is_archived: status.is_archived,
+                        })
+                        .collect();
+                    statuses.sort_by_key(|status| status.order);
+                    statuses
+                },
+                attributes,
+                swimlanes,
+            })
+    }
+
+    // Create a project from one of the templates of the instance, like kanban or scrum
+    pub fn new_project(
+        &mut self,
+        name: String,
+        description: String,
+        template: &str,
+    ) -> Result<TaigaProject> {
+        let templates = self.get::<Vec<TemplateResponse>>("/project-templates")?;
+        let template_slug = slug(template.to_string());
+        let template = templates
+            .iter()
+            .find(|t| t.slug == template_slug || slug(t.name.clone()) == template_slug)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No project template {}, could be: {}",
+                    template,
+                    templates
+                        .iter()
+                        .map(|t| t.slug.clone())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            })?;
+
+        let created = self.post::<ProjectNewRequest, ProjectCreatedResponse>(
+            "/projects",
+            &ProjectNewRequest {
+                name,
+                description,
+                creation_template: template.id,
+            },
+        )?;
+
+        self.get_project(created.id)
+    }
+
+    // Refetch a project after changing it, updating its cache and the one of its tasks
+    pub fn refresh_project(&mut self, id: i32) -> Result<TaigaProject> {
+        let project = self.get_project(id)?;
+        project.save_cache()?;
+
+        if let Some(mut tasks) = TaigaTasks::from_cache(id) {
+            tasks.members = project.members.clone();
+            tasks.statuses = project.statuses.clone();
+            tasks.attributes = project.attributes.clone();
+            tasks.save_cache();
+        }
+
+        Ok(project)
+    }
+
+    // Find a role of the project by name, ignoring the case
+    pub fn get_role(&mut self, id: i32, name: &str) -> Result<i32> {
+        let project = self.get::<ProjectResponse>(&format!("/projects/{}", id))?;
+        let name_slug = slug(name.to_string());
+
+        project
+            .roles
+            .iter()
+            .find(|role| slug(role.name.clone()) == name_slug)
+            .map(|role| role.id)
+            .ok_or_else(|| {
+                anyhow!(
+                    "No role {} in this project, could be: {}",
+                    name,
+                    project
+                        .roles
+                        .iter()
+                        .map(|role| role.name.clone())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
             })
     }
+
+    pub fn get_project_slug(&mut self, id: i32) -> Result<String> {
+        self.get::<ProjectResponse>(&format!("/projects/{}", id))
+            .map(|p| p.slug)
+    }
+
+    // Map every computable role of the project to the point entry with the given value
+    pub fn get_role_points(&mut self, id: i32, value: f64) -> Result<HashMap<String, i32>> {
+        let project = self.get::<ProjectResponse>(&format!("/projects/{}", id))?;
+
+        let point = project
+            .points
+            .iter()
+            .find(|p| p.value == Some(value))
+            .ok_or_else(|| anyhow!("No point value {} in this project", value))?;
+
+        Ok(project
+            .roles
+            .iter()
+            .filter(|role| role.computable)
+            .map(|role| (role.id.to_string(), point.id))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    fn taiga(names: &[&str]) -> Taiga {
+        Taiga {
+            auth_token: String::new(),
+            refresh: String::new(),
+            refresh_time: SystemTime::UNIX_EPOCH,
+            url: String::new(),
+            id: 1,
+            username: String::new(),
+            password: String::new(),
+            projects: names
+                .iter()
+                .enumerate()
+                .map(|(id, name)| TaigaProject {
+                    id: id as i32,
+                    name: name.to_string(),
+                    members: Vec::new(),
+                    statuses: Vec::new(),
+                    attributes: Vec::new(),
+                    swimlanes: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn resolve(taiga: &Taiga, config: &TaigaConfig, name: &str) -> Result<String> {
+        taiga.resolve_project(name, config).map(|p| p.name)
+    }
+
+    #[test]
+    fn reads_member_details() {
+        let member: MemberResponse = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "username": "ann",
+            "full_name_display": "Ann Smith",
+            "role_name": "Back",
+            "is_active": false
+        }))
+        .unwrap();
+        let emails = vec![
+            (8, "bob@example.com".to_string()),
+            (7, "ann@example.com".to_string()),
+        ];
+
+        let user = member.user(&emails);
+        assert_eq!(user.full_name, "Ann Smith");
+        assert_eq!(user.role, "Back");
+        assert_eq!(user.email.as_deref(), Some("ann@example.com"));
+        assert!(!user.is_active);
+
+        // older servers only send the username
+        let member: MemberResponse =
+            serde_json::from_value(serde_json::json!({ "id": 9, "username": "eve" })).unwrap();
+        let user = member.user(&emails);
+        assert_eq!(user.full_name, "");
+        assert_eq!(user.email, None);
+        assert!(user.is_active);
+    }
+
+    #[test]
+    fn resolves_name_slug_and_prefix() {
+        let taiga = taiga(&["Website Redesign", "Mobile App"]);
+        let config = TaigaConfig::default();
+
+        assert_eq!(
+            resolve(&taiga, &config, "Website Redesign").unwrap(),
+            "Website Redesign"
+        );
+        assert_eq!(
+            resolve(&taiga, &config, "website-redesign").unwrap(),
+            "Website Redesign"
+        );
+        assert_eq!(resolve(&taiga, &config, "mob").unwrap(), "Mobile App");
+    }
+
+    #[test]
+    fn resolves_aliases() {
+        let taiga = taiga(&["Website Redesign", "Mobile App"]);
+        let mut config = TaigaConfig::default();
+        config
+            .aliases
+            .insert("web".to_string(), "Mobile App".to_string());
+
+        assert_eq!(resolve(&taiga, &config, "web").unwrap(), "Mobile App");
+    }
+
+    #[test]
+    fn rejects_ambiguous_prefix() {
+        let taiga = taiga(&["Website Redesign", "Website Backend"]);
+        let err = resolve(&taiga, &TaigaConfig::default(), "web").unwrap_err();
+
+        assert!(err.to_string().contains("ambiguous"));
+        assert!(err.to_string().contains("Website Redesign"));
+        assert!(err.to_string().contains("Website Backend"));
+    }
+
+    #[test]
+    fn suggests_close_project() {
+        let taiga = taiga(&["Website Redesign", "Mobile App"]);
+        let err = resolve(&taiga, &TaigaConfig::default(), "mobil-ap").unwrap_err();
+
+        assert!(err.to_string().contains("did you mean 'Mobile App'"));
+    }
+
+    #[test]
+    fn reports_unknown_project() {
+        let taiga = taiga(&["Website Redesign"]);
+        let err = resolve(&taiga, &TaigaConfig::default(), "zzz").unwrap_err();
+
+        assert_eq!(err.to_string(), "Project 'zzz' not found");
+    }
 }
@@ -0,0 +1,179 @@
+use chrono::{Days, Months, NaiveDate};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    static ref INTERVAL_REGEX: Regex = Regex::new(
+        r"^(\d+)(d|day|days|w|wk|wks|week|weeks|m|mth|mths|month|months|y|yr|yrs|year|years)$"
+    )
+    .unwrap();
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaRecurrence {
+    pub task_id: i32,
+    pub name: String,
+    pub status_id: i32,
+    pub assigned: Vec<i32>,
+    pub team: bool,
+    pub client: bool,
+    pub blocked: bool,
+    pub interval: String,
+    pub due: NaiveDate,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaRecurrences {
+    pub id: i32,
+    pub recurrences: Vec<TaigaRecurrence>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RecurUnit {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+// Parse a recurrence interval, either a named period or a relative duration like 2w
+pub fn parse_interval(interval: &str) -> Option<(u32, RecurUnit)> {
+    match interval.to_lowercase().as_str() {
+        "daily" => return Some((1, RecurUnit::Day)),
+        "weekly" => return Some((1, RecurUnit::Week)),
+        "biweekly" => return Some((2, RecurUnit::Week)),
+        "monthly" => return Some((1, RecurUnit::Month)),
+        "quarterly" => return Some((3, RecurUnit::Month)),
+        "yearly" | "annual" => return Some((1, RecurUnit::Year)),
+        _ => {}
+    }
+
+    let input = interval.to_lowercase();
+    let caps = INTERVAL_REGEX.captures(&input)?;
+    let count: u32 = caps[1].parse().ok()?;
+    if count == 0 {
+        return None;
+    }
+
+    let unit = match &caps[2] {
+        "d" | "day" | "days" => RecurUnit::Day,
+        "w" | "wk" | "wks" | "week" | "weeks" => RecurUnit::Week,
+        "m" | "mth" | "mths" | "month" | "months" => RecurUnit::Month,
+        _ => RecurUnit::Year,
+    };
+
+    Some((count, unit))
+}
+
+impl TaigaRecurrence {
+    // Compute the first due date after the current one that is not in the past
+    pub fn next_due(&self, today: NaiveDate) -> Option<NaiveDate> {
+        let (count, unit) = parse_interval(&self.interval)?;
+
+        let mut due = self.due;
+        loop {
+            due = match unit {
+                RecurUnit::Day => due.checked_add_days(Days::new(count as u64))?,
+                RecurUnit::Week => due.checked_add_days(Days::new(7 * count as u64))?,
+                RecurUnit::Month => due.checked_add_months(Months::new(count))?,
+                RecurUnit::Year => due.checked_add_months(Months::new(12 * count))?,
+            };
+            if due >= today {
+                return Some(due);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recurrence(interval: &str, due: NaiveDate) -> TaigaRecurrence {
+        TaigaRecurrence {
+            task_id: 1,
+            name: "Release notes".to_string(),
+            status_id: 1,
+            assigned: Vec::new(),
+            team: false,
+            client: false,
+            blocked: false,
+            interval: interval.to_string(),
+            due,
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_named_intervals() {
+        assert!(matches!(parse_interval("daily"), Some((1, RecurUnit::Day))));
+        assert!(matches!(
+            parse_interval("Weekly"),
+            Some((1, RecurUnit::Week))
+        ));
+        assert!(matches!(
+            parse_interval("biweekly"),
+            Some((2, RecurUnit::Week))
+        ));
+        assert!(matches!(
+            parse_interval("quarterly"),
+            Some((3, RecurUnit::Month))
+        ));
+        assert!(matches!(
+            parse_interval("annual"),
+            Some((1, RecurUnit::Year))
+        ));
+    }
+
+    #[test]
+    fn parses_relative_intervals() {
+        assert!(matches!(parse_interval("3d"), Some((3, RecurUnit::Day))));
+        assert!(matches!(
+            parse_interval("2weeks"),
+            Some((2, RecurUnit::Week))
+        ));
+        assert!(matches!(
+            parse_interval("6mths"),
+            Some((6, RecurUnit::Month))
+        ));
+        assert!(matches!(parse_interval("1YR"), Some((1, RecurUnit::Year))));
+    }
+
+    #[test]
+    fn rejects_invalid_intervals() {
+        assert!(parse_interval("").is_none());
+        assert!(parse_interval("0d").is_none());
+        assert!(parse_interval("w").is_none());
+        assert!(parse_interval("2 weeks").is_none());
+        assert!(parse_interval("fortnightly").is_none());
+        assert!(parse_interval("99999999999d").is_none());
+    }
+
+    #[test]
+    fn next_due_follows_the_interval() {
+        let weekly = recurrence("weekly", date(2026, 10, 16));
+        assert_eq!(
+            weekly.next_due(date(2026, 10, 16)),
+            Some(date(2026, 10, 23))
+        );
+    }
+
+    #[test]
+    fn next_due_skips_missed_occurrences() {
+        let weekly = recurrence("weekly", date(2026, 9, 4));
+        assert_eq!(
+            weekly.next_due(date(2026, 10, 18)),
+            Some(date(2026, 10, 23))
+        );
+    }
+
+    #[test]
+    fn next_due_clamps_month_ends() {
+        let monthly = recurrence("monthly", date(2026, 1, 31));
+        assert_eq!(monthly.next_due(date(2026, 1, 31)), Some(date(2026, 2, 28)));
+    }
+}
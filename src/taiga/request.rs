@@ -1,60 +1,99 @@
 use anyhow::{anyhow, Result};
+use reqwest::blocking::multipart::Form;
 use reqwest::blocking::{Client, RequestBuilder, Response};
 use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::StatusCode;
 use serde::{de::DeserializeOwned, Serialize};
+use std::fmt;
+use std::io::Write;
 
 use super::Taiga;
 
+// Error of a request for a resource the server does not have
+#[derive(Debug)]
+pub struct NotFound;
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Not found")
+    }
+}
+
+impl std::error::Error for NotFound {}
+
 impl Taiga {
     // Core request function that handles retries and authentication
-    fn request<T, R>(&mut self, mut builder: RequestBuilder, body: Option<&T>) -> Result<R>
+    fn request<T, R>(&mut self, builder: RequestBuilder, body: Option<&T>) -> Result<R>
     where
         T: Serialize + ?Sized,
         R: DeserializeOwned,
     {
+        self.send_request(builder, body)?
+            .json()
+            .map_err(|e| anyhow!(e))
+    }
+
+    // Retry a request until it succeeds, returning the response without reading its body
+    fn send_request<T>(&mut self, builder: RequestBuilder, body: Option<&T>) -> Result<Response>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.send_with(|_| {
+            let builder = builder
+                .try_clone()
+                .ok_or_else(|| anyhow!("Could not repeat the request"))?;
+            Ok(match body {
+                Some(body) => builder.json(body),
+                None => builder,
+            })
+        })
+    }
+
+    // Send the request built by the closure with the current token, building it again for
+    // each attempt since some bodies cannot be cloned
+    fn send_with<F>(&mut self, build: F) -> Result<Response>
+    where
+        F: Fn(&Taiga) -> Result<RequestBuilder>,
+    {
+        let attempt = |taiga: &Taiga| -> Result<Response> {
+            taiga
+                .add_auth_header(build(taiga)?)?
+                .send()
+                .map_err(|e| anyhow!(e))
+        };
+
         // First attempt - with current token
-        let mut response = self.execute_request(builder.try_clone().unwrap(), body)?;
+        let response = attempt(self)?;
 
         if response.status().is_success() {
-            return response.json().map_err(|e| anyhow!(e));
+            return Ok(response);
+        }
+
+        // A missing resource stays missing whatever the token
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(NotFound.into());
         }
 
         // Second attempt - try refreshing token
         if let Ok(()) = self.refresh() {
-            builder = self.add_auth_header(builder.try_clone().unwrap())?;
-            response = self.execute_request(builder.try_clone().unwrap(), body)?;
+            let response = attempt(self)?;
 
             if response.status().is_success() {
-                return response.json().map_err(|e| anyhow!(e));
+                return Ok(response);
             }
         }
 
         // Final attempt - full reauth
         self.reauth()?;
-        builder = self.add_auth_header(builder.try_clone().unwrap())?;
-        response = self.execute_request(builder, body)?;
+        let response = attempt(self)?;
 
         if response.status().is_success() {
-            response.json().map_err(|e| anyhow!(e))
+            Ok(response)
         } else {
             Err(anyhow!("Request failed after all retry attempts"))
         }
     }
 
-    // Helper to execute a single request attempt
-    fn execute_request<T>(&self, builder: RequestBuilder, body: Option<&T>) -> Result<Response>
-    where
-        T: Serialize + ?Sized,
-    {
-        let request = if let Some(body) = body {
-            builder.json(body)
-        } else {
-            builder
-        };
-
-        request.send().map_err(|e| anyhow!(e))
-    }
-
     // Add auth header to a request
     fn add_auth_header(&self, builder: RequestBuilder) -> Result<RequestBuilder> {
         Ok(builder.header(
@@ -73,7 +112,6 @@ impl Taiga {
             .get(format!("{}{}", self.url, endpoint))
             .header(CONTENT_TYPE, "application/json")
             .header("x-disable-pagination", "True");
-        let builder = self.add_auth_header(builder)?;
         self.request::<(), R>(builder, None)
     }
 
@@ -86,11 +124,33 @@ impl Taiga {
         let builder = client
             .post(format!("{}{}", self.url, endpoint))
             .header(CONTENT_TYPE, "application/json");
-        let builder = self.add_auth_header(builder)?;
 
         self.request::<T, R>(builder, Some(body))
     }
 
+    // Post to an action endpoint that answers without a body
+    pub fn post_action(&mut self, endpoint: &str) -> Result<()> {
+        let client = Client::new();
+        let builder = client
+            .post(format!("{}{}", self.url, endpoint))
+            .header(CONTENT_TYPE, "application/json");
+
+        self.send_request::<()>(builder, None).map(|_| ())
+    }
+
+    // Post a body to an action endpoint that answers without one
+    pub fn post_action_body<T>(&mut self, endpoint: &str, body: &T) -> Result<()>
+    where
+        T: Serialize + ?Sized,
+    {
+        let client = Client::new();
+        let builder = client
+            .post(format!("{}{}", self.url, endpoint))
+            .header(CONTENT_TYPE, "application/json");
+
+        self.send_request::<T>(builder, Some(body)).map(|_| ())
+    }
+
     pub fn patch<T, R>(&mut self, endpoint: &str, body: &T) -> Result<R>
     where
         T: Serialize + ?Sized,
@@ -100,18 +160,147 @@ impl Taiga {
         let builder = client
             .patch(format!("{}{}", self.url, endpoint))
             .header(CONTENT_TYPE, "application/json");
-        let builder = self.add_auth_header(builder)?;
 
         self.request::<T, R>(builder, Some(body))
     }
 
+    // Deletions answer with an empty body
     pub fn delete(&mut self, endpoint: &str) -> Result<()> {
         let client = Client::new();
         let builder = client
             .delete(format!("{}{}", self.url, endpoint))
             .header(CONTENT_TYPE, "application/json");
-        let builder = self.add_auth_header(builder)?;
 
-        self.request::<(), ()>(builder, None)
+        self.send_request::<()>(builder, None).map(|_| ())
+    }
+
+    // Multipart bodies cannot be cloned, so the form is rebuilt for every attempt
+    pub fn post_multipart<R, F>(&mut self, endpoint: &str, form: F) -> Result<R>
+    where
+        R: DeserializeOwned,
+        F: Fn() -> Result<Form>,
+    {
+        let client = Client::new();
+        let url = format!("{}{}", self.url, endpoint);
+
+        self.send_with(|_| Ok(client.post(&url).multipart(form()?)))?
+            .json()
+            .map_err(|e| anyhow!(e))
+    }
+
+    // Stream a file to the writer instead of loading it in memory
+    pub fn download<W>(&mut self, url: &str, writer: &mut W) -> Result<u64>
+    where
+        W: Write,
+    {
+        let client = Client::new();
+        let mut response = self.send_with(|_| Ok(client.get(url)))?;
+
+        response.copy_to(writer).map_err(|e| anyhow!(e))
+    }
+}
+
+// A local server standing in for Taiga in the tests of the requests
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::SystemTime;
+
+    // Answer each connection with the next response, sending the requests back
+    pub fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str("\r\n");
+                request.push_str(&String::from_utf8_lossy(&body));
+
+                stream.write_all(response.as_bytes()).unwrap();
+                sender.send(request).unwrap();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    pub fn response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    pub fn taiga(url: &str) -> Taiga {
+        Taiga {
+            auth_token: "token".to_string(),
+            refresh: String::new(),
+            refresh_time: SystemTime::now(),
+            url: url.to_string(),
+            id: 1,
+            username: String::new(),
+            password: String::new(),
+            projects: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{response, serve, taiga};
+    use super::*;
+
+    #[test]
+    fn downloads_with_the_token() {
+        let (url, requests) = serve(vec![response("200 OK", "data")]);
+
+        let mut file = Vec::new();
+        let size = taiga(&url)
+            .download(&format!("{}/attachments/1", url), &mut file)
+            .unwrap();
+
+        assert_eq!((size, file.as_slice()), (4, b"data".as_slice()));
+        let head = requests.recv().unwrap().to_lowercase();
+        assert!(head.contains("authorization: bearer token"));
+    }
+
+    #[test]
+    fn multipart_posts_stop_on_not_found() {
+        let (url, requests) = serve(vec![response("404 Not Found", "")]);
+
+        let result = taiga(&url).post_multipart::<serde_json::Value, _>("/attachments", || {
+            Ok(Form::new().text("project", "1"))
+        });
+
+        assert!(result.unwrap_err().is::<NotFound>());
+        assert!(requests.recv().unwrap().starts_with("POST /attachments"));
+        // no refresh nor second attempt followed the 404
+        assert!(requests.try_recv().is_err());
     }
 }
@@ -1,8 +1,110 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use super::Taiga;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TaigaStatus {
     pub id: i32,
     pub slug: String,
     pub is_closed: bool,
+    pub color: String,
+    pub order: i32,
+    pub wip_limit: Option<i32>,
+    pub is_archived: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct StatusResponse {
+    id: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusNewRequest {
+    project: i32,
+    name: String,
+    color: String,
+    is_closed: bool,
+    wip_limit: Option<i32>,
+    order: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusRenameRequest {
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusCloseRequest {
+    is_closed: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct StatusOrderRequest {
+    project: i32,
+    bulk_userstory_statuses: Vec<(i32, i32)>,
+}
+
+impl Taiga {
+    pub fn new_status(
+        &mut self,
+        project_id: i32,
+        name: String,
+        color: String,
+        is_closed: bool,
+        wip_limit: Option<i32>,
+        order: i32,
+    ) -> Result<i32> {
+        self.post::<StatusNewRequest, StatusResponse>(
+            "/userstory-statuses",
+            &StatusNewRequest {
+                project: project_id,
+                name,
+                color,
+                is_closed,
+                wip_limit,
+                order,
+            },
+        )
+        .map(|status| status.id)
+    }
+
+    pub fn rename_status(&mut self, status_id: i32, name: String) -> Result<()> {
+        self.patch::<StatusRenameRequest, StatusResponse>(
+            &format!("/userstory-statuses/{}", status_id),
+            &StatusRenameRequest { name },
+        )
+        .map(|_| ())
+    }
+
+    pub fn close_status(&mut self, status_id: i32, is_closed: bool) -> Result<()> {
+        self.patch::<StatusCloseRequest, StatusResponse>(
+            &format!("/userstory-statuses/{}", status_id),
+            &StatusCloseRequest { is_closed },
+        )
+        .map(|_| ())
+    }
+
+    // Give the statuses their position in the list, the first one going first on the board
+    pub fn reorder_statuses(&mut self, project_id: i32, status_ids: &[i32]) -> Result<()> {
+        self.post_action_body(
+            "/userstory-statuses/bulk_update_order",
+            &StatusOrderRequest {
+                project: project_id,
+                bulk_userstory_statuses: status_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(order, id)| (*id, order as i32 + 1))
+                    .collect(),
+            },
+        )
+    }
+
+    // The stories of a deleted status are moved to another one
+    pub fn delete_status(&mut self, status_id: i32, move_to: i32) -> Result<()> {
+        self.delete(&format!(
+            "/userstory-statuses/{}?moveTo={}",
+            status_id, move_to
+        ))
+    }
 }
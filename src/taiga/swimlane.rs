@@ -0,0 +1,37 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::Taiga;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaSwimlane {
+    pub id: i32,
+    pub name: String,
+    pub order: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct SwimlaneResponse {
+    id: i32,
+    name: String,
+    #[serde(default)]
+    order: i32,
+}
+
+impl Taiga {
+    pub fn get_swimlanes(&mut self, project_id: i32) -> Result<Vec<TaigaSwimlane>> {
+        self.get::<Vec<SwimlaneResponse>>(&format!("/swimlanes?project={}", project_id))
+            .map(|swimlanes| {
+                let mut swimlanes: Vec<TaigaSwimlane> = swimlanes
+                    .into_iter()
+                    .map(|s| TaigaSwimlane {
+                        id: s.id,
+                        name: s.name,
+                        order: s.order,
+                    })
+                    .collect();
+                swimlanes.sort_by_key(|swimlane| swimlane.order);
+                swimlanes
+            })
+    }
+}
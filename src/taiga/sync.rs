@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaSyncStory {
+    pub reference: i32,
+    pub name: String,
+    pub status_id: i32,
+    pub version: i32,
+}
+
+// Stories as they were in the file and on Taiga after the last sync
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaSyncState {
+    pub id: i32,
+    pub path: String,
+    pub stories: Vec<TaigaSyncStory>,
+}
@@ -14,3 +14,14 @@ pub struct Taiga {
     pub password: String,
     pub projects: Vec<TaigaProject>,
 }
+
+impl Taiga {
+    // Address of the web interface matching the api address
+    pub fn web_url(&self) -> String {
+        let url = self.url.trim_end_matches('/');
+        if url == "https://api.taiga.io/api/v1" {
+            return "https://tree.taiga.io".to_string();
+        }
+        url.trim_end_matches("/api/v1").to_string()
+    }
+}
@@ -1,15 +1,18 @@
+use std::collections::HashMap;
 use std::process::exit;
 
 use anyhow::Result;
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::{Taiga, TaigaStatus, TaigaUser};
+use super::request::NotFound;
+use super::{Taiga, TaigaAttribute, TaigaStatus, TaigaUser};
 use crate::utils::slug;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TaigaTask {
     pub id: i32,
+    pub reference: i32,
     pub name: String,
     pub status_id: i32,
     pub status: String,
@@ -18,8 +21,14 @@ pub struct TaigaTask {
     pub blocked: bool,
     pub assigned: Vec<i32>,
     pub due: Option<DateTime<Utc>>,
+    pub swimlane: Option<i32>,
     pub closed: bool,
+    pub tags: Vec<String>,
+    pub watchers: Vec<i32>,
+    pub votes: i32,
     pub version: i32,
+    // custom attribute values by attribute id, only fetched when needed
+    pub attributes: HashMap<i32, String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -28,11 +37,21 @@ pub struct TaigaTasks {
     pub tasks: Vec<TaigaTask>,
     pub members: Vec<TaigaUser>,
     pub statuses: Vec<TaigaStatus>,
+    pub attributes: Vec<TaigaAttribute>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaUuids {
+    pub id: i32,
+    pub uuids: HashMap<i32, String>,
 }
 
 #[derive(Deserialize, Debug)]
+#[allow(dead_code)]
 struct UserStory {
     id: i32,
+    #[serde(rename = "ref")]
+    reference: i32,
     subject: String,
     status: i32,
     status_extra_info: UserStoryStatus,
@@ -41,7 +60,17 @@ struct UserStory {
     is_blocked: bool,
     assigned_users: Vec<i32>,
     due_date: Option<String>,
+    #[serde(default)]
+    swimlane: Option<i32>,
     is_closed: bool,
+    #[serde(default)]
+    tags: Vec<(String, Option<String>)>,
+    #[serde(default)]
+    watchers: Vec<i32>,
+    #[serde(default)]
+    total_voters: i32,
+    #[serde(default)]
+    description: Option<String>,
     version: i32,
 }
 
@@ -56,6 +85,26 @@ struct TaskStatusRequest {
     version: i32,
 }
 
+#[derive(Debug, Serialize)]
+struct TaskBulkNewRequest {
+    project_id: i32,
+    status_id: i32,
+    bulk_stories: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskBulkStatusRequest {
+    project_id: i32,
+    status_id: i32,
+    bulk_userstories: Vec<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TaskBulkStatusResponse {
+    id: i32,
+    status: i32,
+}
+
 #[derive(Debug, Serialize)]
 struct TaskRenameRequest {
     subject: String,
@@ -92,10 +141,29 @@ struct TaskBlockRequest {
     version: i32,
 }
 
+#[derive(Debug, Serialize)]
+struct TaskCommentRequest {
+    comment: String,
+    version: i32,
+}
+
+#[derive(Debug, Serialize)]
+struct TaskDetailRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    points: HashMap<String, i32>,
+    version: i32,
+}
+
 #[derive(Debug, Serialize)]
 struct TaskModifyRequest {
     status: i32,
     subject: String,
+    // the web interface shows the first assignee, so it is kept in step with the list
+    assigned_to: Option<i32>,
     assigned_users: Vec<i32>,
     due_date: Option<String>,
     team_requirement: bool,
@@ -107,6 +175,7 @@ struct TaskModifyRequest {
 #[derive(Debug, Serialize)]
 struct TaskNewRequest {
     assigned_to: Option<i32>,
+    assigned_users: Vec<i32>,
     client_requirement: bool,
     is_blocked: bool,
     project: i32,
@@ -124,6 +193,78 @@ impl Taiga {
         .map(|ts| ts.iter().map(TaigaTask::new).collect())
     }
 
+    // Stories of every status, archived ones included
+    pub fn get_all_tasks(&mut self, id: i32) -> Result<Vec<TaigaTask>> {
+        self.get::<Vec<UserStory>>(&format!("/userstories?project={}", id))
+            .map(|ts| ts.iter().map(TaigaTask::new).collect())
+    }
+
+    pub fn get_open_tasks(&mut self, id: i32) -> Result<Vec<TaigaTask>> {
+        self.get::<Vec<UserStory>>(&format!(
+            "/userstories?project={}&status__is_archived=false&status__is_closed=false",
+            id
+        ))
+        .map(|ts| ts.iter().map(TaigaTask::new).collect())
+    }
+
+    pub fn get_task(&mut self, task_id: i32) -> Result<TaigaTask> {
+        self.get::<UserStory>(&format!("/userstories/{}", task_id))
+            .map(|t| TaigaTask::new(&t))
+    }
+
+    // Get a task, or None when it does not exist anymore
+    pub fn find_task(&mut self, task_id: i32) -> Result<Option<TaigaTask>> {
+        match self.get_task(task_id) {
+            Ok(task) => Ok(Some(task)),
+            Err(err) if err.is::<NotFound>() => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn get_task_by_ref(&mut self, project_id: i32, reference: i32) -> Result<TaigaTask> {
+        self.get::<UserStory>(&format!(
+            "/userstories/by_ref?ref={}&project={}",
+            reference, project_id
+        ))
+        .map(|t| TaigaTask::new(&t))
+    }
+
+    pub fn comment_task(
+        &mut self,
+        task_id: i32,
+        comment: String,
+        version: i32,
+    ) -> Result<TaigaTask> {
+        self.patch::<TaskCommentRequest, UserStory>(
+            &format!("/userstories/{}", task_id),
+            &TaskCommentRequest { comment, version },
+        )
+        .map(|t| TaigaTask::new(&t))
+    }
+
+    // Get a task along with its description, which the task list does not carry
+    pub fn get_task_description(&mut self, task_id: i32) -> Result<(TaigaTask, String)> {
+        self.get::<UserStory>(&format!("/userstories/{}", task_id))
+            .map(|t| {
+                (
+                    TaigaTask::new(&t),
+                    t.description.clone().unwrap_or_default(),
+                )
+            })
+    }
+
+    pub fn watch_task(&mut self, task_id: i32, remove: bool) -> Result<TaigaTask> {
+        let action = if remove { "unwatch" } else { "watch" };
+        self.post_action(&format!("/userstories/{}/{}", task_id, action))?;
+        self.get_task(task_id)
+    }
+
+    pub fn vote_task(&mut self, task_id: i32, remove: bool) -> Result<TaigaTask> {
+        let action = if remove { "downvote" } else { "upvote" };
+        self.post_action(&format!("/userstories/{}/{}", task_id, action))?;
+        self.get_task(task_id)
+    }
+
     pub fn move_task(&mut self, task_id: i32, status_id: i32, version: i32) -> Result<TaigaTask> {
         self.patch::<TaskStatusRequest, UserStory>(
             &format!("/userstories/{}", task_id),
@@ -135,6 +276,30 @@ impl Taiga {
         .map(|t| TaigaTask::new(&t))
     }
 
+    // Move tasks to a status at once, returning the ids of the ones the server moved
+    pub fn bulk_move_tasks(
+        &mut self,
+        project_id: i32,
+        status_id: i32,
+        task_ids: Vec<i32>,
+    ) -> Result<Vec<i32>> {
+        self.post::<TaskBulkStatusRequest, Vec<TaskBulkStatusResponse>>(
+            "/userstories/bulk_update_kanban_order",
+            &TaskBulkStatusRequest {
+                project_id,
+                status_id,
+                bulk_userstories: task_ids,
+            },
+        )
+        .map(|moved| {
+            moved
+                .into_iter()
+                .filter(|story| story.status == status_id)
+                .map(|story| story.id)
+                .collect()
+        })
+    }
+
     pub fn delete_task(&mut self, task_id: i32) -> Result<()> {
         self.delete(&format!("/userstories/{}", task_id))
             .map(|_| ())
@@ -216,6 +381,27 @@ impl Taiga {
         .map(|t| TaigaTask::new(&t))
     }
 
+    pub fn detail_task(
+        &mut self,
+        task_id: i32,
+        description: Option<String>,
+        tags: Option<Vec<String>>,
+        points: HashMap<String, i32>,
+        version: i32,
+    ) -> Result<TaigaTask> {
+        self.patch::<TaskDetailRequest, UserStory>(
+            &format!("/userstories/{}", task_id),
+            &TaskDetailRequest {
+                description,
+                tags,
+                points,
+                version,
+            },
+        )
+        .map(|t| TaigaTask::new(&t))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn modify_task(
         &mut self,
         task_id: i32,
@@ -233,6 +419,7 @@ impl Taiga {
             &TaskModifyRequest {
                 status,
                 subject: rename,
+                assigned_to: assign.first().copied(),
                 assigned_users: assign,
                 due_date,
                 team_requirement: team,
@@ -244,6 +431,7 @@ impl Taiga {
         .map(|t| TaigaTask::new(&t))
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_task(
         &mut self,
         project_id: i32,
@@ -261,6 +449,7 @@ impl Taiga {
                 status,
                 subject: name,
                 assigned_to: assign.first().copied(),
+                assigned_users: assign,
                 team_requirement: team,
                 client_requirement: client,
                 is_blocked: block,
@@ -269,6 +458,23 @@ impl Taiga {
         .map(|t| TaigaTask::new(&t))
     }
 
+    pub fn bulk_new_tasks(
+        &mut self,
+        project_id: i32,
+        status_id: i32,
+        names: Vec<String>,
+    ) -> Result<Vec<TaigaTask>> {
+        self.post::<TaskBulkNewRequest, Vec<UserStory>>(
+            "/userstories/bulk_create",
+            &TaskBulkNewRequest {
+                project_id,
+                status_id,
+                bulk_stories: names.join("\n"),
+            },
+        )
+        .map(|ts| ts.iter().map(TaigaTask::new).collect())
+    }
+
     pub fn update_tasks(&mut self, id: i32, tasks: TaigaTasks) -> TaigaTasks {
         let project = self.get_project(id).unwrap_or_else(|err| {
             eprintln!("Error, could not get project: {}", err);
@@ -280,6 +486,7 @@ impl Taiga {
             tasks: tasks.tasks.clone(),
             members: project.members,
             statuses: project.statuses,
+            attributes: project.attributes,
         };
 
         tasks.clone().save_cache();
@@ -292,6 +499,7 @@ impl TaigaTask {
     fn new(t: &UserStory) -> TaigaTask {
         TaigaTask {
             id: t.id,
+            reference: t.reference,
             name: t.subject.clone(),
             status_id: t.status,
             status: slug(t.status_extra_info.name.clone()),
@@ -299,14 +507,130 @@ impl TaigaTask {
             client: t.client_requirement,
             blocked: t.is_blocked,
             assigned: t.assigned_users.clone(),
-            due: t.due_date.as_ref().and_then(|date| {
-                format!("{date} 00:00:00")
-                    .parse::<NaiveDateTime>()
-                    .ok()
-                    .map(|dt| Utc.from_utc_datetime(&dt))
-            }),
-            closed: false,
+            due: parse_due_date(&t.due_date),
+            swimlane: t.swimlane,
+            closed: t.is_closed,
+            tags: t.tags.iter().map(|(tag, _)| tag.clone()).collect(),
+            watchers: t.watchers.clone(),
+            votes: t.total_voters,
             version: t.version,
+            attributes: HashMap::new(),
         }
     }
 }
+
+// Due dates come as plain dates and are kept as midnight UTC
+pub fn parse_due_date(date: &Option<String>) -> Option<DateTime<Utc>> {
+    date.as_ref().and_then(|date| {
+        format!("{date} 00:00:00")
+            .parse::<NaiveDateTime>()
+            .ok()
+            .map(|dt| Utc.from_utc_datetime(&dt))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::taiga::request::testing::{response, serve, taiga};
+
+    fn story_json(extra: serde_json::Value) -> serde_json::Value {
+        let mut story = serde_json::json!({
+            "id": 40,
+            "ref": 12,
+            "subject": "Fix login",
+            "status": 2,
+            "status_extra_info": { "name": "In progress" },
+            "team_requirement": false,
+            "client_requirement": false,
+            "is_blocked": false,
+            "assigned_users": [7],
+            "due_date": null,
+            "is_closed": false,
+            "version": 3
+        });
+        story
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        story
+    }
+
+    fn story(extra: serde_json::Value) -> TaigaTask {
+        TaigaTask::new(&serde_json::from_value(story_json(extra)).unwrap())
+    }
+
+    #[test]
+    fn reads_closed_stories() {
+        assert!(!story(serde_json::json!({})).closed);
+        assert!(story(serde_json::json!({ "is_closed": true })).closed);
+    }
+
+    #[test]
+    fn reads_watchers_and_votes() {
+        let task = story(serde_json::json!({ "watchers": [7, 9], "total_voters": 4 }));
+        assert_eq!(task.watchers, vec![7, 9]);
+        assert_eq!(task.votes, 4);
+
+        // older servers leave them out
+        let task = story(serde_json::json!({}));
+        assert!(task.watchers.is_empty());
+        assert_eq!(task.votes, 0);
+    }
+
+    #[test]
+    fn detail_request_sends_cleared_tags() {
+        let request = |tags| TaskDetailRequest {
+            description: None,
+            tags,
+            points: HashMap::new(),
+            version: 3,
+        };
+
+        let cleared = serde_json::to_value(request(Some(Vec::new()))).unwrap();
+        assert_eq!(cleared, serde_json::json!({ "tags": [], "version": 3 }));
+
+        let untouched = serde_json::to_value(request(None)).unwrap();
+        assert_eq!(untouched, serde_json::json!({ "version": 3 }));
+    }
+
+    #[test]
+    fn modify_requests_keep_the_main_assignee() {
+        let stories = vec![
+            response("200 OK", &story_json(serde_json::json!({})).to_string()),
+            response("200 OK", &story_json(serde_json::json!({})).to_string()),
+        ];
+        let (url, requests) = serve(stories);
+        let mut taiga = taiga(&url);
+
+        let modify = |taiga: &mut Taiga, assign: Vec<i32>| {
+            taiga
+                .modify_task(
+                    40,
+                    2,
+                    "Fix login".to_string(),
+                    assign,
+                    None,
+                    false,
+                    false,
+                    true,
+                    3,
+                )
+                .unwrap();
+            let request = requests.recv().unwrap();
+            let (head, body) = request.split_once("\r\n\r\n").unwrap();
+            assert!(head.starts_with("PATCH /userstories/40"));
+            serde_json::from_str::<serde_json::Value>(body).unwrap()
+        };
+
+        let body = modify(&mut taiga, vec![7, 4]);
+        assert_eq!(body["assigned_to"], serde_json::json!(7));
+        assert_eq!(body["assigned_users"], serde_json::json!([7, 4]));
+        assert_eq!(body["is_blocked"], serde_json::json!(true));
+        assert_eq!(body["version"], serde_json::json!(3));
+
+        let body = modify(&mut taiga, Vec::new());
+        assert_eq!(body["assigned_to"], serde_json::Value::Null);
+        assert_eq!(body["assigned_users"], serde_json::json!([]));
+    }
+}
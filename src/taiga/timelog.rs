@@ -0,0 +1,147 @@
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+lazy_static! {
+    static ref DURATION_REGEX: Regex = Regex::new(r"^(?:(\d+)h)?(?:(\d+)m(?:in)?)?$").unwrap();
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaTimeEntry {
+    pub project_id: i32,
+    pub project: String,
+    pub task_id: i32,
+    pub reference: i32,
+    pub name: String,
+    pub started: DateTime<Utc>,
+    pub minutes: i64,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaigaTimer {
+    pub project_id: i32,
+    pub project: String,
+    pub task_id: i32,
+    pub reference: i32,
+    pub name: String,
+    pub started: DateTime<Utc>,
+}
+
+// The running timer and the logged time of every project
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TaigaTimeLog {
+    pub timer: Option<TaigaTimer>,
+    pub entries: Vec<TaigaTimeEntry>,
+}
+
+impl TaigaTimer {
+    // Turn the timer into an entry ending now, rounded to the minute
+    pub fn stop(self, now: DateTime<Utc>, note: Option<String>) -> TaigaTimeEntry {
+        let minutes = ((now - self.started).num_seconds() + 30) / 60;
+
+        TaigaTimeEntry {
+            project_id: self.project_id,
+            project: self.project,
+            task_id: self.task_id,
+            reference: self.reference,
+            name: self.name,
+            started: self.started,
+            minutes: minutes.max(0),
+            note,
+        }
+    }
+}
+
+impl TaigaTimeLog {
+    pub fn task_minutes(&self, task_id: i32) -> i64 {
+        self.entries
+            .iter()
+            .filter(|entry| entry.task_id == task_id)
+            .map(|entry| entry.minutes)
+            .sum()
+    }
+}
+
+// Parse a duration such as 1h30m, 2h, 45m or 90 into minutes
+pub fn parse_minutes(input: &str) -> Option<i64> {
+    if let Ok(minutes) = input.parse::<i64>() {
+        return Some(minutes).filter(|minutes| *minutes > 0);
+    }
+
+    let input = input.to_lowercase();
+    let caps = DURATION_REGEX.captures(&input)?;
+    let hours: i64 = caps.get(1).map_or(Some(0), |h| h.as_str().parse().ok())?;
+    let minutes: i64 = caps.get(2).map_or(Some(0), |m| m.as_str().parse().ok())?;
+
+    hours
+        .checked_mul(60)
+        .and_then(|hours| hours.checked_add(minutes))
+        .filter(|minutes| *minutes > 0)
+}
+
+pub fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{}m", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_minutes("90"), Some(90));
+        assert_eq!(parse_minutes("45m"), Some(45));
+        assert_eq!(parse_minutes("45min"), Some(45));
+        assert_eq!(parse_minutes("2h"), Some(120));
+        assert_eq!(parse_minutes("1h30m"), Some(90));
+        assert_eq!(parse_minutes("1H30M"), Some(90));
+        assert_eq!(parse_minutes("0h90m"), Some(90));
+    }
+
+    #[test]
+    fn rejects_invalid_durations() {
+        assert_eq!(parse_minutes(""), None);
+        assert_eq!(parse_minutes("0"), None);
+        assert_eq!(parse_minutes("0h0m"), None);
+        assert_eq!(parse_minutes("-5"), None);
+        assert_eq!(parse_minutes("1.5h"), None);
+        assert_eq!(parse_minutes("30m1h"), None);
+        assert_eq!(parse_minutes("1h 30m"), None);
+        assert_eq!(parse_minutes("999999999999999999h"), None);
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_minutes(45), "45m");
+        assert_eq!(format_minutes(120), "2h");
+        assert_eq!(format_minutes(90), "1h30m");
+        assert_eq!(parse_minutes(&format_minutes(605)), Some(605));
+    }
+
+    #[test]
+    fn stops_timers_to_the_nearest_minute() {
+        let started = Utc.with_ymd_and_hms(2026, 10, 18, 9, 0, 0).unwrap();
+        let timer = || TaigaTimer {
+            project_id: 1,
+            project: "Web".to_string(),
+            task_id: 7,
+            reference: 12,
+            name: "Fix login".to_string(),
+            started,
+        };
+
+        let entry = timer().stop(started + chrono::Duration::seconds(89), None);
+        assert_eq!(entry.minutes, 1);
+        let entry = timer().stop(started + chrono::Duration::seconds(90), None);
+        assert_eq!(entry.minutes, 2);
+        let entry = timer().stop(started - chrono::Duration::seconds(90), None);
+        assert_eq!(entry.minutes, 0);
+    }
+}
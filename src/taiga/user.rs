@@ -1,7 +1,169 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 
+use super::Taiga;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TaigaUser {
     pub id: i32,
     pub username: String,
+    pub full_name: String,
+    pub role: String,
+    // only visible to the administrators of the project
+    pub email: Option<String>,
+    pub is_active: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct MembershipResponse {
+    id: i32,
+    #[serde(default)]
+    user: Option<i32>,
+    #[serde(default)]
+    user_email: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct MembershipNewRequest {
+    project: i32,
+    role: i32,
+    username: String,
+}
+
+// Find a member from their username, full name or email, ignoring the case, or else from a
+// unique prefix of their username
+pub fn find_member<'a>(members: &'a [TaigaUser], name: &str) -> Result<&'a TaigaUser> {
+    if let Some(member) = members.iter().find(|m| m.username == name) {
+        return Ok(member);
+    }
+
+    let name_lower = name.to_lowercase();
+    let matches: Vec<&TaigaUser> = members
+        .iter()
+        .filter(|m| {
+            m.username.to_lowercase() == name_lower
+                || m.full_name.to_lowercase() == name_lower
+                || m.email
+                    .as_ref()
+                    .is_some_and(|email| email.to_lowercase() == name_lower)
+        })
+        .collect();
+    let candidates: Vec<&TaigaUser> = if matches.is_empty() {
+        members
+            .iter()
+            .filter(|m| m.username.to_lowercase().starts_with(&name_lower))
+            .collect()
+    } else {
+        matches
+    };
+
+    match candidates.as_slice() {
+        [member] => Ok(member),
+        [] => Err(anyhow!("could not find user {} on the project", name)),
+        candidates => Err(anyhow!(
+            "user {} is ambiguous, could be: {}",
+            name,
+            candidates
+                .iter()
+                .map(|m| m.username.clone())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )),
+    }
+}
+
+impl Taiga {
+    // Invite someone by email, they become a member once they accept
+    pub fn invite_member(&mut self, project_id: i32, email: String, role_id: i32) -> Result<()> {
+        self.post::<MembershipNewRequest, MembershipResponse>(
+            "/memberships",
+            &MembershipNewRequest {
+                project: project_id,
+                role: role_id,
+                username: email,
+            },
+        )
+        .map(|_| ())
+    }
+
+    // The email of each member whose email the user is allowed to see
+    pub fn get_member_emails(&mut self, project_id: i32) -> Result<Vec<(i32, String)>> {
+        self.get::<Vec<MembershipResponse>>(&format!("/memberships?project={}", project_id))
+            .map(|memberships| {
+                memberships
+                    .into_iter()
+                    .filter_map(|membership| Some((membership.user?, membership.user_email?)))
+                    .filter(|(_, email)| !email.is_empty())
+                    .collect()
+            })
+    }
+
+    pub fn remove_member(&mut self, project_id: i32, user_id: i32) -> Result<()> {
+        let membership = self
+            .get::<Vec<MembershipResponse>>(&format!("/memberships?project={}", project_id))?
+            .into_iter()
+            .find(|membership| membership.user == Some(user_id))
+            .ok_or_else(|| anyhow!("No membership for this user"))?;
+
+        self.delete(&format!("/memberships/{}", membership.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(id: i32, username: &str, full_name: &str, email: Option<&str>) -> TaigaUser {
+        TaigaUser {
+            id,
+            username: username.to_string(),
+            full_name: full_name.to_string(),
+            role: String::new(),
+            email: email.map(String::from),
+            is_active: true,
+        }
+    }
+
+    fn members() -> Vec<TaigaUser> {
+        vec![
+            member(1, "ann", "Ann Smith", Some("ann@example.com")),
+            member(2, "annabel", "Annabel Lee", None),
+            member(3, "Bob", "Robert Jones", Some("bob@example.com")),
+        ]
+    }
+
+    fn find(name: &str) -> Result<i32> {
+        find_member(&members(), name).map(|member| member.id)
+    }
+
+    #[test]
+    fn finds_members_by_exact_username() {
+        // ann is also a prefix of annabel
+        assert_eq!(find("ann").unwrap(), 1);
+        assert_eq!(find("annabel").unwrap(), 2);
+    }
+
+    #[test]
+    fn finds_members_ignoring_the_case() {
+        assert_eq!(find("bob").unwrap(), 3);
+        assert_eq!(find("ANN").unwrap(), 1);
+        assert_eq!(find("robert jones").unwrap(), 3);
+        assert_eq!(find("Ann@Example.com").unwrap(), 1);
+    }
+
+    #[test]
+    fn finds_members_by_unique_prefix() {
+        assert_eq!(find("anna").unwrap(), 2);
+        assert_eq!(find("BO").unwrap(), 3);
+    }
+
+    #[test]
+    fn rejects_ambiguous_and_unknown_members() {
+        let err = find("an").unwrap_err().to_string();
+        assert!(err.contains("ambiguous"));
+        assert!(err.contains("ann, annabel"));
+
+        let err = find("eve").unwrap_err().to_string();
+        assert!(err.contains("could not find user eve"));
+    }
 }
@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::task::parse_due_date;
+use super::{Taiga, TaigaProject, TaigaStatus, TaigaTask, TaigaTasks};
+use crate::utils::slug;
+
+#[derive(Debug, Clone)]
+pub struct TaigaWebhookEvent {
+    pub action: String,
+    pub reference: i32,
+    pub name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct WebhookPayload {
+    action: String,
+    #[serde(rename = "type")]
+    kind: String,
+    data: Value,
+}
+
+#[derive(Deserialize, Debug)]
+struct WebhookStory {
+    id: i32,
+    #[serde(rename = "ref")]
+    reference: i32,
+    subject: String,
+    project: WebhookProject,
+    status: WebhookStatus,
+    #[serde(default)]
+    team_requirement: bool,
+    #[serde(default)]
+    client_requirement: bool,
+    #[serde(default)]
+    is_blocked: bool,
+    #[serde(default)]
+    assigned_users: Vec<i32>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    swimlane: Value,
+    #[serde(default)]
+    tags: Vec<Value>,
+    #[serde(default)]
+    watchers: Vec<i32>,
+    #[serde(default)]
+    total_voters: i32,
+    #[serde(default)]
+    version: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WebhookProject {
+    id: i32,
+}
+
+#[derive(Deserialize, Debug)]
+struct WebhookStatus {
+    id: i32,
+    name: String,
+    #[serde(default)]
+    slug: Option<String>,
+    #[serde(default)]
+    is_closed: bool,
+    #[serde(default)]
+    color: String,
+}
+
+impl Taiga {
+    // Apply a webhook payload to the cached project and tasks, ignoring anything but stories
+    pub fn apply_webhook(&mut self, body: &str) -> Result<Option<TaigaWebhookEvent>> {
+        let payload: WebhookPayload =
+            serde_json::from_str(body).context("Could not parse webhook payload")?;
+        if payload.kind != "userstory" {
+            return Ok(None);
+        }
+
+        let story: WebhookStory =
+            serde_json::from_value(payload.data).context("Could not parse webhook story")?;
+        let project_id = story.project.id;
+
+        let event = TaigaWebhookEvent {
+            action: payload.action.clone(),
+            reference: story.reference,
+            name: story.subject.clone(),
+        };
+
+        let mut tasks = TaigaTasks::from_cache(project_id);
+
+        if payload.action == "delete" {
+            if let Some(mut tasks) = tasks {
+                tasks.tasks.retain(|task| task.id != story.id);
+                tasks.save_cache();
+            }
+            return Ok(Some(event));
+        }
+
+        if payload.action != "create" && payload.action != "change" {
+            return Err(anyhow!("unknown webhook action {}", payload.action));
+        }
+
+        // the version is needed for later edits, so fetch the story when the payload lacks it
+        let task = match story.version {
+            Some(version) => story_task(&story, version),
+            None => self.get_task(story.id)?,
+        };
+
+        if let Some(project) = self.update_project_cache(&story)? {
+            if let Some(tasks) = tasks.as_mut() {
+                tasks.members = project.members;
+                tasks.statuses = project.statuses;
+                tasks.attributes = project.attributes;
+            }
+        }
+
+        if let Some(mut tasks) = tasks {
+            match tasks.tasks.iter_mut().find(|t| t.id == task.id) {
+                Some(cached) => *cached = task,
+                // new stories go last so that the ids of the last search stay valid
+                None if payload.action == "create" => tasks.tasks.push(task),
+                None => {}
+            }
+            tasks.save_cache();
+        }
+
+        Ok(Some(event))
+    }
+
+    // Refresh the cached project when the story refers to a status or member it does not know
+    fn update_project_cache(&mut self, story: &WebhookStory) -> Result<Option<TaigaProject>> {
+        let mut project = match TaigaProject::from_cache(story.project.id) {
+            Ok(Some(project)) => project,
+            _ => return Ok(None),
+        };
+
+        let known_members = story
+            .assigned_users
+            .iter()
+            .all(|id| project.members.iter().any(|member| member.id == *id));
+        if !known_members {
+            project = self.get_project(story.project.id)?;
+        } else if project.statuses.iter().any(|s| s.id == story.status.id) {
+            return Ok(None);
+        }
+
+        if !project.statuses.iter().any(|s| s.id == story.status.id) {
+            // the payload lacks the position and limit, which the next project fetch brings
+            let order = project.statuses.iter().map(|s| s.order + 1).max();
+            project.statuses.push(TaigaStatus {
+                id: story.status.id,
+                slug: story
+                    .status
+                    .slug
+                    .clone()
+                    .unwrap_or_else(|| slug(story.status.name.clone())),
+                is_closed: story.status.is_closed,
+                color: story.status.color.clone(),
+                order: order.unwrap_or_default(),
+                wip_limit: None,
+                is_archived: false,
+            });
+        }
+
+        project.save_cache()?;
+        Ok(Some(project))
+    }
+}
+
+fn story_task(story: &WebhookStory, version: i32) -> TaigaTask {
+    TaigaTask {
+        id: story.id,
+        reference: story.reference,
+        name: story.subject.clone(),
+        status_id: story.status.id,
+        status: slug(story.status.name.clone()),
+        team: story.team_requirement,
+        client: story.client_requirement,
+        blocked: story.is_blocked,
+        assigned: story.assigned_users.clone(),
+        due: parse_due_date(&story.due_date),
+        // the swimlane is either an id or an object, depending on the Taiga version
+        swimlane: story
+            .swimlane
+            .as_i64()
+            .or_else(|| story.swimlane.get("id").and_then(Value::as_i64))
+            .map(|id| id as i32),
+        closed: story.status.is_closed,
+        // tags are either plain names or [name, color] pairs
+        tags: story
+            .tags
+            .iter()
+            .filter_map(|tag| match tag {
+                Value::String(name) => Some(name.clone()),
+                Value::Array(pair) => pair.first().and_then(Value::as_str).map(String::from),
+                _ => None,
+            })
+            .collect(),
+        watchers: story.watchers.clone(),
+        votes: story.total_voters,
+        version,
+        attributes: HashMap::new(),
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha1::{Digest, Sha1};
+use std::collections::HashSet;
+use uuid::Builder;
+
+use crate::taiga::{TaigaStatus, TaigaTask, TaigaUser};
+use crate::utils::slug;
+
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskwarriorTask {
+    pub uuid: String,
+    pub description: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taigaref: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taigastatus: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub taigaassigned: Option<String>,
+    // every other attribute is kept so that the task can be imported back unchanged
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+impl TaskwarriorTask {
+    // Convert a story, using the status slug as tag and the Taiga fields as UDAs
+    pub fn from_task(
+        task: &TaigaTask,
+        uuid: String,
+        project: &str,
+        statuses: &[TaigaStatus],
+        members: &[TaigaUser],
+    ) -> Self {
+        let closed = statuses
+            .iter()
+            .any(|status| status.id == task.status_id && status.is_closed);
+
+        let mut tags = vec![task.status.clone()];
+        if task.team {
+            tags.push("team".to_string());
+        }
+        if task.client {
+            tags.push("client".to_string());
+        }
+        if task.blocked {
+            tags.push("blocked".to_string());
+        }
+        for tag in &task.tags {
+            tags.push(slug(tag.clone()));
+        }
+        let mut seen = HashSet::new();
+        tags.retain(|tag| seen.insert(tag.clone()));
+
+        let assigned: Vec<String> = task
+            .assigned
+            .iter()
+            .filter_map(|id| members.iter().find(|member| member.id == *id))
+            .map(|member| member.username.clone())
+            .collect();
+
+        TaskwarriorTask {
+            uuid,
+            description: task.name.clone(),
+            status: if closed { "completed" } else { "pending" }.to_string(),
+            project: Some(project.to_string()),
+            due: task.due.map(|due| format_date(due.date_naive())),
+            tags,
+            taigaref: Some(task.reference),
+            taigastatus: Some(task.status.clone()),
+            taigaassigned: if assigned.is_empty() {
+                None
+            } else {
+                Some(assigned.join(","))
+            },
+            other: Map::new(),
+        }
+    }
+
+    pub fn completed(&self) -> bool {
+        self.status == "completed"
+    }
+
+    pub fn deleted(&self) -> bool {
+        self.status == "deleted" || self.status == "recurring"
+    }
+
+    // Taskwarrior dates are UTC instants, the day is the one they fall on locally
+    pub fn due_date(&self) -> Option<Result<NaiveDate, String>> {
+        self.due.as_ref().map(|due| {
+            NaiveDateTime::parse_from_str(due, DATE_FORMAT)
+                .map(|dt| {
+                    Utc.from_utc_datetime(&dt)
+                        .with_timezone(&Local)
+                        .date_naive()
+                })
+                .map_err(|_| format!("could not parse due date {}", due))
+        })
+    }
+
+    pub fn assigned(&self) -> Vec<String> {
+        self.taigaassigned
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|username| username.trim().to_string())
+            .filter(|username| !username.is_empty())
+            .collect()
+    }
+}
+
+// Parse the output of `task export`, either a JSON array or one task per line
+pub fn parse_tasks(input: &str) -> Result<Vec<TaskwarriorTask>, String> {
+    if input.trim_start().starts_with('[') {
+        return serde_json::from_str(input).map_err(|err| err.to_string());
+    }
+
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line.trim().trim_end_matches(','))
+                .map_err(|err| format!("line {}: {}", i + 1, err))
+        })
+        .collect()
+}
+
+// Derive a stable uuid for a story so that repeated exports update the same task
+pub fn story_uuid(project_id: i32, reference: i32) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(format!("taiga-{}-{}", project_id, reference).as_bytes());
+    let hash = hasher.finalize();
+
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    Builder::from_sha1_bytes(bytes).into_uuid().to_string()
+}
+
+// Write a day as its local midnight, the way taskwarrior stores dates
+fn format_date(date: NaiveDate) -> String {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+        .map(|midnight| midnight.with_timezone(&Utc))
+        .unwrap_or_else(|| Utc.from_utc_datetime(&date.and_time(Default::default())))
+        .format(DATE_FORMAT)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(due: Option<&str>) -> TaskwarriorTask {
+        TaskwarriorTask {
+            uuid: story_uuid(1, 12),
+            description: "Fix login".to_string(),
+            status: "pending".to_string(),
+            project: None,
+            due: due.map(|due| due.to_string()),
+            tags: Vec::new(),
+            taigaref: None,
+            taigastatus: None,
+            taigaassigned: None,
+            other: Map::new(),
+        }
+    }
+
+    #[test]
+    fn reads_due_dates_in_local_time() {
+        let due = task(Some("20261020T220000Z")).due_date().unwrap().unwrap();
+        let expected = Utc
+            .with_ymd_and_hms(2026, 10, 20, 22, 0, 0)
+            .unwrap()
+            .with_timezone(&Local)
+            .date_naive();
+        assert_eq!(due, expected);
+    }
+
+    #[test]
+    fn round_trips_due_dates() {
+        for day in [1, 20, 31] {
+            let date = NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+            let due = task(Some(&format_date(date))).due_date().unwrap().unwrap();
+            assert_eq!(due, date);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_due_dates() {
+        assert!(task(Some("2026-10-20")).due_date().unwrap().is_err());
+        assert!(task(None).due_date().is_none());
+    }
+
+    #[test]
+    fn splits_assigned_usernames() {
+        let mut task = task(None);
+        task.taigaassigned = Some("alice, bob,,".to_string());
+        assert_eq!(task.assigned(), vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn parses_json_arrays_and_lines() {
+        let array = r#"[{"uuid":"a","description":"One","status":"pending","taigaref":3}]"#;
+        let tasks = parse_tasks(array).unwrap();
+        assert_eq!(tasks[0].taigaref, Some(3));
+
+        let lines = "{\"uuid\":\"a\",\"description\":\"One\",\"status\":\"pending\"},\n\
+                     {\"uuid\":\"b\",\"description\":\"Two\",\"status\":\"completed\",\"urgency\":2}\n";
+        let tasks = parse_tasks(lines).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(tasks[1].completed());
+        assert_eq!(tasks[1].other.get("urgency"), Some(&Value::from(2)));
+
+        assert!(parse_tasks("{\"uuid\":\"a\"}")
+            .unwrap_err()
+            .starts_with("line 1"));
+    }
+
+    #[test]
+    fn keeps_unknown_attributes_on_export() {
+        let input = r#"{"uuid":"a","description":"One","status":"pending","priority":"H"}"#;
+        let tasks = parse_tasks(input).unwrap();
+        let output = serde_json::to_value(&tasks[0]).unwrap();
+        assert_eq!(output["priority"], "H");
+        assert!(output.get("taigaref").is_none());
+    }
+
+    #[test]
+    fn exports_stories_with_distinct_tags() {
+        let story = TaigaTask {
+            id: 7,
+            reference: 12,
+            name: "Fix login".to_string(),
+            status_id: 2,
+            status: "done".to_string(),
+            team: true,
+            client: false,
+            blocked: false,
+            assigned: Vec::new(),
+            due: None,
+            swimlane: None,
+            closed: true,
+            tags: vec!["team".to_string(), "ui".to_string(), "done".to_string()],
+            watchers: Vec::new(),
+            votes: 0,
+            version: 1,
+            attributes: Default::default(),
+        };
+        let status = TaigaStatus {
+            id: 2,
+            slug: "done".to_string(),
+            is_closed: true,
+            color: String::new(),
+            order: 1,
+            wip_limit: None,
+            is_archived: false,
+        };
+
+        let task = TaskwarriorTask::from_task(&story, story_uuid(1, 12), "web", &[status], &[]);
+        assert_eq!(task.tags, vec!["done", "team", "ui"]);
+        assert!(task.completed());
+        assert_eq!(task.taigaref, Some(12));
+        assert!(task.due.is_none());
+    }
+
+    #[test]
+    fn derives_stable_uuids() {
+        assert_eq!(story_uuid(1, 12), story_uuid(1, 12));
+        assert_ne!(story_uuid(1, 12), story_uuid(1, 13));
+        assert_ne!(story_uuid(1, 12), story_uuid(2, 12));
+        assert_eq!(story_uuid(1, 12).len(), 36);
+    }
+}
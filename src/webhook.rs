@@ -0,0 +1,210 @@
+use sha1::{Digest, Sha1};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+const SIGNATURE_HEADER: &str = "x-taiga-webhook-signature";
+const MAX_BODY: usize = 10 * 1024 * 1024;
+const BLOCK_SIZE: usize = 64;
+
+#[derive(Debug)]
+pub struct WebhookRequest {
+    pub signature: Option<String>,
+    pub body: Vec<u8>,
+}
+
+// Read a single POST request, only keeping the signature header and the body
+pub fn read_request(stream: &TcpStream) -> Result<WebhookRequest, (u16, String)> {
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .map_err(|err| (400, err.to_string()))?;
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|err| (400, err.to_string()))?;
+    if !request_line.starts_with("POST ") {
+        return Err((405, format!("unexpected request {}", request_line.trim())));
+    }
+
+    let mut signature = None;
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        reader
+            .read_line(&mut line)
+            .map_err(|err| (400, err.to_string()))?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_lowercase().as_str() {
+                SIGNATURE_HEADER => signature = Some(value.trim().to_string()),
+                "content-length" => length = value.trim().parse::<usize>().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    let length = length.ok_or_else(|| (411, "missing content length".to_string()))?;
+    if length > MAX_BODY {
+        return Err((413, format!("body of {} bytes is too large", length)));
+    }
+
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| (400, err.to_string()))?;
+
+    Ok(WebhookRequest { signature, body })
+}
+
+pub fn respond(mut stream: &TcpStream, code: u16) {
+    let reason = match code {
+        200 => "OK",
+        401 => "Unauthorized",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        _ => "Bad Request",
+    };
+    // the sender may already be gone, there is nobody to report that to
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        code, reason
+    );
+}
+
+// Taiga signs the body with a hex encoded HMAC-SHA1 of the webhook key
+pub fn verify_signature(key: &str, body: &[u8], signature: Option<&str>) -> bool {
+    let signature = match signature {
+        Some(signature) => signature.to_lowercase(),
+        None => return false,
+    };
+    let expected: String = hmac_sha1(key.as_bytes(), body)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    // compare every byte so that the time taken does not leak the signature
+    expected.len() == signature.len()
+        && expected
+            .bytes()
+            .zip(signature.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn hmac_sha1(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        let hash = Sha1::digest(key);
+        block[..hash.len()].copy_from_slice(&hash);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha1::new();
+    inner.update(block.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+    inner.update(message);
+
+    let mut outer = Sha1::new();
+    outer.update(block.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+    outer.update(inner.finalize());
+
+    outer.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // test cases of RFC 2202, section 3
+    #[test]
+    fn hmac_sha1_matches_rfc_2202() {
+        let cases: Vec<(Vec<u8>, Vec<u8>, &str)> = vec![
+            (
+                vec![0x0b; 20],
+                b"Hi There".to_vec(),
+                "b617318655057264e28bc0b6fb378c8ef146be00",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+            ),
+            (
+                vec![0xaa; 20],
+                vec![0xdd; 50],
+                "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+            ),
+            (
+                (1..=25).collect(),
+                vec![0xcd; 50],
+                "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+            ),
+            (
+                vec![0x0c; 20],
+                b"Test With Truncation".to_vec(),
+                "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+            ),
+            (
+                vec![0xaa; 80],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+                "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+            ),
+            (
+                vec![0xaa; 80],
+                b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data"
+                    .to_vec(),
+                "e8e99d0f45237d786d6bbaa7965c7808bbff1a91",
+            ),
+        ];
+
+        for (key, data, digest) in cases {
+            assert_eq!(hex(&hmac_sha1(&key, &data)), digest);
+        }
+    }
+
+    #[test]
+    fn accepts_valid_signatures() {
+        let signature = "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79";
+        let body = b"what do ya want for nothing?";
+
+        assert!(verify_signature("Jefe", body, Some(signature)));
+        assert!(verify_signature(
+            "Jefe",
+            body,
+            Some(&signature.to_uppercase())
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_signatures() {
+        let signature = "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79";
+        let body = b"what do ya want for nothing?";
+
+        assert!(!verify_signature("Jefe", body, None));
+        assert!(!verify_signature("Jefe", body, Some("")));
+        assert!(!verify_signature("Jefe", body, Some(&signature[..38])));
+        assert!(!verify_signature("jefe", body, Some(signature)));
+        assert!(!verify_signature(
+            "Jefe",
+            b"what do ya want for nothing!",
+            Some(signature)
+        ));
+        assert!(!verify_signature(
+            "Jefe",
+            body,
+            Some("effcdf6ae5eb2fa2d27416d5f184df9c259a7c7a")
+        ));
+    }
+}